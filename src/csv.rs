@@ -2,7 +2,12 @@
 
 pub(crate) mod reader;
 
-pub use reader::infer_schema;
 pub use reader::Config;
-pub use reader::FieldParser;
-pub use reader::{Reader, Record};
+pub use reader::{
+    infer_schema, infer_schema_sampled, infer_schema_with_config, infer_schema_with_header,
+};
+pub use reader::{parser_by_name, FieldParser, PARSER_NAMES};
+pub use reader::{
+    DefaultValue, OnParseError, ParseError, ParseFailure, Reader, Record,
+    DEFAULT_PARALLELISM_THRESHOLD,
+};
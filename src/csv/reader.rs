@@ -1,7 +1,10 @@
 use crate::record;
-use arrow::array::{Array, BinaryBuilder, PrimitiveBuilder, StringBuilder};
+use arrow::array::{
+    Array, ArrayBuilder, BinaryBuilder, BooleanBuilder, PrimitiveBuilder, StringBuilder,
+};
 use arrow::datatypes::{
-    ArrowPrimitiveType, DataType, Field, Float64Type, Int64Type, Schema, UInt32Type,
+    ArrowPrimitiveType, DataType, Field, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, Schema, UInt32Type, UInt64Type,
 };
 use arrow::error::ArrowError;
 use csv_core::ReadRecordResult;
@@ -34,7 +37,7 @@ impl Record {
             outlen += nout;
             endlen += nend;
             match res {
-                ReadRecordResult::InputEmpty => continue,
+                ReadRecordResult::InputEmpty => {}
                 ReadRecordResult::OutputFull => {
                     fields.resize(std::cmp::max(4, fields.len().checked_mul(2).unwrap()), 0);
                 }
@@ -54,25 +57,38 @@ impl Record {
         }
     }
 
+    /// Reads a record from `input`. Returns `Ok(None)` at the end of the
+    /// input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from `input` fails. The error is returned
+    /// as is, except that reads interrupted by a signal are retried.
+    ///
     /// # Panics
     ///
     /// Panics if line length in input * 2 overflows `usize`.
-    ///
-    #[must_use]
-    pub fn from_buf(reader: &mut csv_core::Reader, input: &mut dyn BufRead) -> Option<Self> {
+    pub fn from_buf(
+        reader: &mut csv_core::Reader,
+        input: &mut dyn BufRead,
+    ) -> std::io::Result<Option<Self>> {
         let mut fields = Vec::with_capacity(1024);
         let mut ends = Vec::with_capacity(1024);
         let (mut outlen, mut endlen) = (0, 0);
         loop {
             let (res, nin, nout, nend) = {
-                let buf = input.fill_buf().expect("file reading error");
+                let buf = match input.fill_buf() {
+                    Ok(buf) => buf,
+                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e),
+                };
                 reader.read_record(buf, &mut fields[outlen..], &mut ends[endlen..])
             };
             input.consume(nin);
             outlen += nout;
             endlen += nend;
             match res {
-                ReadRecordResult::InputEmpty => continue,
+                ReadRecordResult::InputEmpty => {}
                 ReadRecordResult::OutputFull => {
                     fields.resize(std::cmp::max(4, fields.len().checked_mul(2).unwrap()), 0);
                 }
@@ -84,11 +100,39 @@ impl Record {
                         fields.set_len(outlen);
                         ends.set_len(endlen);
                     }
-                    return Some(Self { fields, ends });
+                    return Ok(Some(Self { fields, ends }));
                 }
-                ReadRecordResult::End => return None,
+                ReadRecordResult::End => return Ok(None),
+            }
+        }
+    }
+
+    /// Splits `input` into fields separated by `delimiter`, which may be
+    /// longer than one byte, e.g., `||`. Quotes are not recognized, so a field
+    /// cannot contain `delimiter`. A trailing `\n` or `\r\n` is removed.
+    ///
+    /// Returns `None` if `input` is empty, except for the line terminator.
+    #[must_use]
+    pub fn split(input: &[u8], delimiter: &[u8]) -> Option<Self> {
+        let input = input
+            .strip_suffix(b"\n")
+            .map_or(input, |line| line.strip_suffix(b"\r").unwrap_or(line));
+        if input.is_empty() {
+            return None;
+        }
+        let mut fields = Vec::with_capacity(input.len());
+        let mut ends = Vec::new();
+        let mut rest = input;
+        if !delimiter.is_empty() {
+            while let Some(pos) = rest.windows(delimiter.len()).position(|w| w == delimiter) {
+                fields.extend_from_slice(&rest[..pos]);
+                ends.push(fields.len());
+                rest = &rest[pos + delimiter.len()..];
             }
         }
+        fields.extend_from_slice(rest);
+        ends.push(fields.len());
+        Some(Self { fields, ends })
     }
 
     #[inline]
@@ -113,10 +157,51 @@ impl Record {
             .map(|(&start, &end)| &self.fields[start..end])
             .collect()
     }
+
+    /// Joins the fields with `delimiter` into a CSV line without a line
+    /// terminator.
+    ///
+    /// A field containing `delimiter`, `"`, `\r`, or `\n` is enclosed in
+    /// double quotes, with each `"` in it doubled. The result may differ from
+    /// the original input if it quoted fields that do not need quoting, or
+    /// used a different quote character.
+    #[must_use]
+    pub fn reassemble(&self, delimiter: u8) -> Vec<u8> {
+        let mut line = Vec::with_capacity(self.fields.len() + self.ends.len());
+        for (i, field) in self.fields().into_iter().enumerate() {
+            if i > 0 {
+                line.push(delimiter);
+            }
+            if field
+                .iter()
+                .any(|&b| b == delimiter || b == b'"' || b == b'\r' || b == b'\n')
+            {
+                line.push(b'"');
+                for &b in field {
+                    if b == b'"' {
+                        line.push(b'"');
+                    }
+                    line.push(b);
+                }
+                line.push(b'"');
+            } else {
+                line.extend_from_slice(field);
+            }
+        }
+        line
+    }
 }
 
 pub struct ParseError {
-    inner: Box<dyn std::error::Error>,
+    inner: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl ParseError {
+    fn invalid(message: &'static str) -> Self {
+        Self {
+            inner: message.into(),
+        }
+    }
 }
 
 impl fmt::Debug for ParseError {
@@ -125,6 +210,12 @@ impl fmt::Debug for ParseError {
     }
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
 impl From<std::net::AddrParseError> for ParseError {
     fn from(error: std::net::AddrParseError) -> Self {
         Self {
@@ -166,8 +257,14 @@ impl From<std::str::Utf8Error> for ParseError {
 }
 
 pub type Int64Parser = dyn Fn(&[u8]) -> Result<i64, ParseError> + Send + Sync;
+pub type Int32Parser = dyn Fn(&[u8]) -> Result<i32, ParseError> + Send + Sync;
+pub type Int16Parser = dyn Fn(&[u8]) -> Result<i16, ParseError> + Send + Sync;
+pub type Int8Parser = dyn Fn(&[u8]) -> Result<i8, ParseError> + Send + Sync;
 pub type UInt32Parser = dyn Fn(&[u8]) -> Result<u32, ParseError> + Send + Sync;
+pub type UInt64Parser = dyn Fn(&[u8]) -> Result<u64, ParseError> + Send + Sync;
 pub type Float64Parser = dyn Fn(&[u8]) -> Result<f64, ParseError> + Send + Sync;
+pub type Float32Parser = dyn Fn(&[u8]) -> Result<f32, ParseError> + Send + Sync;
+pub type BooleanParser = dyn Fn(&[u8]) -> Result<bool, ParseError> + Send + Sync;
 
 /// A parser for a single field in CSV.
 #[derive(Clone)]
@@ -175,18 +272,40 @@ pub enum FieldParser {
     /// A parser converting a byte sequence into `i64`.
     Int64(Arc<Int64Parser>),
 
+    /// A parser converting a byte sequence into `i32`.
+    Int32(Arc<Int32Parser>),
+
+    /// A parser converting a byte sequence into `i16`.
+    Int16(Arc<Int16Parser>),
+
+    /// A parser converting a byte sequence into `i8`.
+    Int8(Arc<Int8Parser>),
+
     /// A parser converting a byte sequence into `u32`.
     UInt32(Arc<UInt32Parser>),
 
+    /// A parser converting a byte sequence into `u64`.
+    UInt64(Arc<UInt64Parser>),
+
     /// A parser converting a byte sequence into `f64`.
     Float64(Arc<Float64Parser>),
 
+    /// A parser converting a byte sequence into `f32`.
+    Float32(Arc<Float32Parser>),
+
     /// A parser reading the input into a UTF-8 string.
+    ///
+    /// Control characters, including NUL, are valid UTF-8 and are kept as
+    /// they are.
     Utf8,
 
-    /// A dummy parser that preserves the input byte sequence.
+    /// A dummy parser that preserves the input byte sequence, including any
+    /// NUL bytes.
     Binary,
 
+    /// A parser converting a byte sequence into `bool`.
+    Boolean(Arc<BooleanParser>),
+
     /// A timestamp parser converting time into `i64`.
     Timestamp(Arc<Int64Parser>),
 }
@@ -198,18 +317,132 @@ impl FieldParser {
         Self::Int64(Arc::new(parse::<i64>))
     }
 
+    /// Creates an `i32` parser. A value out of the range of `i32` is an
+    /// error.
+    #[must_use]
+    pub fn int32() -> Self {
+        Self::Int32(Arc::new(parse::<i32>))
+    }
+
+    /// Creates an `i16` parser. A value out of the range of `i16` is an
+    /// error.
+    #[must_use]
+    pub fn int16() -> Self {
+        Self::Int16(Arc::new(parse::<i16>))
+    }
+
+    /// Creates an `i8` parser. A value out of the range of `i8` is an error.
+    #[must_use]
+    pub fn int8() -> Self {
+        Self::Int8(Arc::new(parse::<i8>))
+    }
+
     /// Creates a `u32` parser.
     #[must_use]
     pub fn uint32() -> Self {
         Self::UInt32(Arc::new(parse::<u32>))
     }
 
+    /// Creates a `u64` parser.
+    #[must_use]
+    pub fn uint64() -> Self {
+        Self::UInt64(Arc::new(parse::<u64>))
+    }
+
+    /// Creates an `i64` parser that also accepts hexadecimal, octal, and
+    /// binary integers prefixed with `0x`, `0o`, and `0b`, respectively.
+    ///
+    /// The prefix is case-insensitive and may follow a `+` or `-` sign, as in
+    /// `-0x1F`. A field without a prefix is decimal, so each field in a column
+    /// may use a different radix.
+    #[must_use]
+    pub fn int64_radix_aware() -> Self {
+        Self::Int64(Arc::new(parse_radix_aware_i64))
+    }
+
+    /// Creates an `i64` parser for ISO 8601 durations, such as `PT1H30M`,
+    /// converting them into the number of seconds.
+    ///
+    /// Weeks (`W`), days (`D`), hours (`H`), minutes (`M` after `T`), and
+    /// seconds (`S`) with integer values are accepted, in this order, and a
+    /// leading `-` negates the duration. Years and months are rejected because
+    /// their lengths vary, and so are fractions and any other malformed
+    /// input.
+    #[must_use]
+    pub fn iso8601_duration() -> Self {
+        Self::Int64(Arc::new(parse_iso8601_duration))
+    }
+
     /// Creates a `f64` parser.
     #[must_use]
     pub fn float64() -> Self {
         Self::Float64(Arc::new(parse::<f64>))
     }
 
+    /// Creates a `bool` parser accepting `true` and `1` as true, and `false`
+    /// and `0` as false, ignoring case.
+    #[must_use]
+    pub fn boolean() -> Self {
+        Self::boolean_with_tokens(&["true", "1"], &["false", "0"])
+    }
+
+    /// Creates a `bool` parser accepting `truthy` as true and `falsy` as
+    /// false, ignoring ASCII case. Any other value cannot be parsed.
+    #[must_use]
+    pub fn boolean_with_tokens(truthy: &[&str], falsy: &[&str]) -> Self {
+        let lowercase = |tokens: &[&str]| {
+            tokens
+                .iter()
+                .map(|t| t.to_ascii_lowercase().into_bytes())
+                .collect::<Vec<_>>()
+        };
+        let (truthy, falsy) = (lowercase(truthy), lowercase(falsy));
+        Self::Boolean(Arc::new(move |v: &[u8]| {
+            let v = v.to_ascii_lowercase();
+            if truthy.contains(&v) {
+                Ok(true)
+            } else if falsy.contains(&v) {
+                Ok(false)
+            } else {
+                Err(ParseError::invalid("not a boolean token"))
+            }
+        }))
+    }
+
+    /// Creates a `f32` parser.
+    #[must_use]
+    pub fn float32() -> Self {
+        Self::Float32(Arc::new(parse::<f32>))
+    }
+
+    /// Creates a `f64` parser that accepts finite numbers only. NaN and
+    /// infinity, e.g., `nan` or `-inf`, are errors, handled as set by
+    /// [`Reader::with_on_parse_error`]. A signed zero, e.g., `-0.0`, keeps
+    /// its sign.
+    #[must_use]
+    pub fn finite_float64() -> Self {
+        Self::Float64(Arc::new(parse_finite::<f64>))
+    }
+
+    /// Creates a `f32` parser that accepts finite numbers only, like
+    /// [`FieldParser::finite_float64`].
+    #[must_use]
+    pub fn finite_float32() -> Self {
+        Self::Float32(Arc::new(parse_finite::<f32>))
+    }
+
+    /// Creates a `f64` parser for currency amounts, such as `$1,234.56`.
+    ///
+    /// A leading currency symbol and comma grouping separators are removed,
+    /// and an amount in parentheses, such as `(500.00)`, is negative. The
+    /// parser is strict: misplaced grouping separators, a sign combined with
+    /// parentheses, or any other ambiguous input is an error. Use
+    /// [`FieldParser::Utf8`] to keep the original string.
+    #[must_use]
+    pub fn currency() -> Self {
+        Self::Float64(Arc::new(parse_currency))
+    }
+
     /// Creates a timestamp parser that converts time into the number of
     /// non-leap seconds since the midnight on January 1, 1970.
     #[must_use]
@@ -217,6 +450,17 @@ impl FieldParser {
         Self::Int64(Arc::new(parse_timestamp))
     }
 
+    /// Creates a timestamp parser that also accepts the number of seconds
+    /// since the Unix epoch.
+    ///
+    /// A field is parsed as by [`FieldParser::timestamp`] first, and only if
+    /// that fails, as an integer number of seconds. Either way, the result is
+    /// in the same unit as that of [`FieldParser::timestamp`].
+    #[must_use]
+    pub fn lenient_timestamp() -> Self {
+        Self::Int64(Arc::new(parse_lenient_timestamp))
+    }
+
     /// Creates a custom `u32` parser.
     #[must_use]
     pub fn uint32_with_parser<P>(parser: P) -> Self
@@ -226,6 +470,15 @@ impl FieldParser {
         Self::UInt32(Arc::new(parser))
     }
 
+    /// Creates a custom `u64` parser.
+    #[must_use]
+    pub fn uint64_with_parser<P>(parser: P) -> Self
+    where
+        P: Fn(&[u8]) -> Result<u64, ParseError> + Send + Sync + 'static,
+    {
+        Self::UInt64(Arc::new(parser))
+    }
+
     /// Creates a custom timestamp parser.
     #[must_use]
     pub fn timestamp_with_parser<P>(parser: P) -> Self
@@ -236,41 +489,321 @@ impl FieldParser {
     }
 }
 
+/// The names of the built-in parsers accepted by [`parser_by_name`].
+pub const PARSER_NAMES: [&str; 19] = [
+    "binary",
+    "boolean",
+    "currency",
+    "finite_float32",
+    "finite_float64",
+    "float32",
+    "float64",
+    "int16",
+    "int32",
+    "int64",
+    "int64_radix_aware",
+    "int8",
+    "ipaddr",
+    "iso8601_duration",
+    "lenient_timestamp",
+    "timestamp",
+    "uint32",
+    "uint64",
+    "utf8",
+];
+
+/// Returns the built-in parser with the given name, one of [`PARSER_NAMES`],
+/// or `None` if there is no such parser.
+///
+/// `"ipaddr"` parses an IPv4 address into `u32`, and the others are the
+/// parsers created by the `FieldParser` constructors of the same names.
+#[must_use]
+pub fn parser_by_name(name: &str) -> Option<FieldParser> {
+    let parser = match name {
+        "binary" => FieldParser::Binary,
+        "boolean" => FieldParser::boolean(),
+        "currency" => FieldParser::currency(),
+        "finite_float32" => FieldParser::finite_float32(),
+        "finite_float64" => FieldParser::finite_float64(),
+        "float32" => FieldParser::float32(),
+        "float64" => FieldParser::float64(),
+        "int16" => FieldParser::int16(),
+        "int32" => FieldParser::int32(),
+        "int64" => FieldParser::int64(),
+        "int64_radix_aware" => FieldParser::int64_radix_aware(),
+        "int8" => FieldParser::int8(),
+        "ipaddr" => FieldParser::uint32_with_parser(|v| {
+            Ok(str::from_utf8(v)?.parse::<std::net::Ipv4Addr>()?.into())
+        }),
+        "iso8601_duration" => FieldParser::iso8601_duration(),
+        "lenient_timestamp" => FieldParser::lenient_timestamp(),
+        "timestamp" => FieldParser::timestamp(),
+        "uint32" => FieldParser::uint32(),
+        "uint64" => FieldParser::uint64(),
+        "utf8" => FieldParser::Utf8,
+        _ => return None,
+    };
+    Some(parser)
+}
+
 impl fmt::Debug for FieldParser {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Int64(_) => write!(f, "Int64"),
+            Self::Int32(_) => write!(f, "Int32"),
+            Self::Int16(_) => write!(f, "Int16"),
+            Self::Int8(_) => write!(f, "Int8"),
             Self::UInt32(_) => write!(f, "UInt32"),
+            Self::UInt64(_) => write!(f, "UInt64"),
             Self::Float64(_) => write!(f, "Float64"),
+            Self::Float32(_) => write!(f, "Float32"),
             Self::Utf8 => write!(f, "Utf8"),
             Self::Binary => write!(f, "Binary"),
+            Self::Boolean(_) => write!(f, "Boolean"),
             Self::Timestamp(_) => write!(f, "Timestamp"),
         }
     }
 }
 
+/// Parses a field as `T`, retrying without the surrounding double quotes if
+/// the field is quoted, as it is when the CSV reader does not handle quotes.
 fn parse<T>(v: &[u8]) -> Result<T, ParseError>
 where
     T: FromStr,
     <T as FromStr>::Err: Into<ParseError>,
 {
-    std::str::from_utf8(v)?.parse::<T>().map_err(Into::into)
+    let s = std::str::from_utf8(v)?;
+    s.parse::<T>().or_else(|e| {
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(unquoted) => unquoted.parse::<T>(),
+            None => Err(e),
+        }
+        .map_err(Into::into)
+    })
+}
+
+/// Parses a field as a finite floating-point number, like `parse`.
+fn parse_finite<T>(v: &[u8]) -> Result<T, ParseError>
+where
+    T: FromStr + num_traits::Float,
+    <T as FromStr>::Err: Into<ParseError>,
+{
+    let value = parse::<T>(v)?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ParseError::invalid("not a finite number"))
+    }
+}
+
+const CURRENCY_SYMBOLS: [char; 5] = ['$', '€', '£', '¥', '₩'];
+
+/// Parses a currency amount such as `$1,234.56`, `-$5`, or `(500.00)`.
+fn parse_currency(v: &[u8]) -> Result<f64, ParseError> {
+    let mut s = str::from_utf8(v)?.trim();
+    let parenthesized = match (s.strip_prefix('('), s.ends_with(')')) {
+        (Some(inner), true) => {
+            s = &inner[..inner.len() - 1];
+            true
+        }
+        (None, false) => false,
+        _ => return Err(ParseError::invalid("unbalanced parentheses")),
+    };
+
+    let mut negative = false;
+    let (mut sign_seen, mut symbol_seen) = (false, false);
+    loop {
+        if let Some(rest) = s.strip_prefix(CURRENCY_SYMBOLS) {
+            if symbol_seen {
+                return Err(ParseError::invalid("multiple currency symbols"));
+            }
+            symbol_seen = true;
+            s = rest;
+        } else if let Some(rest) = s.strip_prefix(['-', '+']) {
+            if parenthesized || sign_seen {
+                return Err(ParseError::invalid("ambiguous sign"));
+            }
+            sign_seen = true;
+            negative = s.starts_with('-');
+            s = rest;
+        } else {
+            break;
+        }
+    }
+
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let mut groups = integer.split(',');
+    let first = groups.next().unwrap_or_default();
+    let grouped = integer.contains(',');
+    if first.is_empty()
+        || (grouped && first.len() > 3)
+        || groups.any(|g| g.len() != 3)
+        || !integer.bytes().all(|b| b.is_ascii_digit() || b == b',')
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(ParseError::invalid("invalid currency amount"));
+    }
+
+    let amount = format!("{}.{fraction}", integer.replace(',', "")).parse::<f64>()?;
+    if parenthesized || negative {
+        Ok(-amount)
+    } else {
+        Ok(amount)
+    }
+}
+
+/// The formats with a numeric UTC offset accepted by [`parse_timestamp`]:
+/// RFC 3339, and the same with a space instead of `T`. Fractional seconds
+/// are optional.
+const TIMESTAMP_FORMATS_WITH_OFFSET: [&str; 2] =
+    ["%Y-%m-%dT%H:%M:%S%.f%:z", "%Y-%m-%d %H:%M:%S%.f%:z"];
+
+/// The formats in UTC accepted by [`parse_timestamp`]: those of
+/// [`TIMESTAMP_FORMATS_WITH_OFFSET`] with `Z` or without any offset instead.
+const TIMESTAMP_FORMATS_IN_UTC: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.fZ",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.fZ",
+    "%Y-%m-%d %H:%M:%S%.f",
+];
+
+/// Parses a timestamp in one of [`TIMESTAMP_FORMATS_WITH_OFFSET`], applying
+/// the offset, or of [`TIMESTAMP_FORMATS_IN_UTC`], and returns it in UTC.
+fn parse_utc_timestamp(s: &str) -> Result<chrono::NaiveDateTime, chrono::format::ParseError> {
+    let with_offset = |fmt| chrono::DateTime::parse_from_str(s, fmt).map(|t| t.naive_utc());
+    TIMESTAMP_FORMATS_WITH_OFFSET
+        .into_iter()
+        .map(with_offset)
+        .chain(
+            TIMESTAMP_FORMATS_IN_UTC
+                .into_iter()
+                .map(|fmt| chrono::NaiveDateTime::parse_from_str(s, fmt)),
+        )
+        .find(Result::is_ok)
+        // Reports the error of RFC 3339, the primary format.
+        .unwrap_or_else(|| with_offset(TIMESTAMP_FORMATS_WITH_OFFSET[0]))
+}
+
+/// Parses an integer in decimal, or in the radix given by a `0x`, `0o`, or
+/// `0b` prefix after an optional sign.
+fn parse_radix_aware_i64(v: &[u8]) -> Result<i64, ParseError> {
+    let s = str::from_utf8(v)?;
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let radix = match unsigned.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => return parse::<i64>(v),
+    };
+    let digits = &unsigned[2..];
+    if digits.starts_with(['+', '-']) {
+        return Err(ParseError::invalid("sign after radix prefix"));
+    }
+    let magnitude = i128::from(u64::from_str_radix(digits, radix)?);
+    i64::try_from(if negative { -magnitude } else { magnitude })
+        .map_err(|_| ParseError::invalid("integer out of range"))
+}
+
+/// The designators of ISO 8601 durations accepted by
+/// [`FieldParser::iso8601_duration`] in order, with their lengths in seconds
+/// and whether they come after `T`.
+const DURATION_UNITS: [(u8, i64, bool); 5] = [
+    (b'W', 604_800, false),
+    (b'D', 86_400, false),
+    (b'H', 3_600, true),
+    (b'M', 60, true),
+    (b'S', 1, true),
+];
+
+/// Parses an ISO 8601 duration into the number of seconds.
+fn parse_iso8601_duration(v: &[u8]) -> Result<i64, ParseError> {
+    let (negative, v) = match v.strip_prefix(b"-") {
+        Some(rest) => (true, rest),
+        None => (false, v),
+    };
+    let Some(mut rest) = v.strip_prefix(b"P") else {
+        return Err(ParseError::invalid("duration must start with P"));
+    };
+    let mut seconds: i64 = 0;
+    // The index of the first unit allowed next in `DURATION_UNITS`.
+    let mut next_unit = 0;
+    let mut has_time = false;
+    let mut empty = true;
+    while let Some((&first, tail)) = rest.split_first() {
+        if first == b'T' {
+            if has_time {
+                return Err(ParseError::invalid("duplicate T in duration"));
+            }
+            has_time = true;
+            next_unit = 2;
+            rest = tail;
+            if rest.is_empty() {
+                return Err(ParseError::invalid("no time after T in duration"));
+            }
+            continue;
+        }
+        let len = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        let (digits, tail) = rest.split_at(len);
+        let Some((&designator, tail)) = tail.split_first() else {
+            return Err(ParseError::invalid("number without a unit in duration"));
+        };
+        if digits.is_empty() {
+            return Err(ParseError::invalid("unit without a number in duration"));
+        }
+        let Some(position) = DURATION_UNITS[next_unit..]
+            .iter()
+            .position(|&(d, _, time)| d == designator && time == has_time)
+        else {
+            return Err(ParseError::invalid("unexpected unit in duration"));
+        };
+        let (_, unit_seconds, _) = DURATION_UNITS[next_unit + position];
+        next_unit += position + 1;
+        seconds = parse::<i64>(digits)?
+            .checked_mul(unit_seconds)
+            .and_then(|s| seconds.checked_add(s))
+            .ok_or_else(|| ParseError::invalid("duration out of range"))?;
+        empty = false;
+        rest = tail;
+    }
+    if empty {
+        return Err(ParseError::invalid("empty duration"));
+    }
+    Ok(if negative { -seconds } else { seconds })
 }
 
-/// Parses timestamp in RFC 3339 format.
+/// Parses timestamp in RFC 3339 format, or one of its common variants.
 fn parse_timestamp(v: &[u8]) -> Result<i64, ParseError> {
-    Ok(
-        chrono::NaiveDateTime::parse_from_str(str::from_utf8(v)?, "%Y-%m-%dT%H:%M:%S%.f%:z")?
-            .and_utc()
-            .timestamp_nanos_opt()
-            .unwrap_or_default(),
-    )
+    Ok(parse_utc_timestamp(str::from_utf8(v)?)?
+        .and_utc()
+        .timestamp_nanos_opt()
+        .unwrap_or_default())
+}
+
+/// Parses timestamp as [`parse_timestamp`] does, falling back to the number of
+/// seconds since the Unix epoch.
+fn parse_lenient_timestamp(v: &[u8]) -> Result<i64, ParseError> {
+    parse_timestamp(v).or_else(|_| {
+        parse::<i64>(v)?
+            .checked_mul(1_000_000_000)
+            .ok_or_else(|| ParseError::invalid("epoch seconds out of range"))
+    })
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Config {
     delimiter: u8,
     quote: u8,
+    /// The byte that ends a record. If `None`, a record ends with `\n`,
+    /// `\r`, or `\r\n`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    terminator: Option<u8>,
+    /// The byte that escapes a quote in a quoted field, in addition to
+    /// doubling the quote. If `None`, only doubling is recognized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    escape: Option<u8>,
 }
 
 impl Default for Config {
@@ -278,6 +811,8 @@ impl Default for Config {
         Config {
             delimiter: b',',
             quote: b'"',
+            terminator: None,
+            escape: None,
         }
     }
 }
@@ -287,16 +822,167 @@ impl From<Config> for csv_core::ReaderBuilder {
         let mut builder = csv_core::ReaderBuilder::new();
         builder.delimiter(config.delimiter);
         builder.quote(config.quote);
+        if let Some(terminator) = config.terminator {
+            builder.terminator(csv_core::Terminator::Any(terminator));
+        }
+        builder.escape(config.escape);
         builder
     }
 }
 
+/// The delimiters [`Config::detect`] chooses from, in the order of
+/// preference.
+const DELIMITER_CANDIDATES: [u8; 4] = [b',', b'\t', b'|', b';'];
+
 impl Config {
     #[must_use]
     pub fn delimiter(&self) -> u8 {
         self.delimiter
     }
+
+    /// Sets the byte that separates fields, instead of `,`.
+    #[must_use]
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Returns the byte that quotes a field.
+    #[must_use]
+    pub fn quote(&self) -> u8 {
+        self.quote
+    }
+
+    /// Sets the byte that quotes a field, instead of `"`.
+    #[must_use]
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Returns the byte that ends a record, or `None` if a record ends with
+    /// `\n`, `\r`, or `\r\n`.
+    #[must_use]
+    pub fn terminator(&self) -> Option<u8> {
+        self.terminator
+    }
+
+    /// Sets the byte that ends a record, instead of `\n`, `\r`, or `\r\n`.
+    #[must_use]
+    pub fn with_terminator(mut self, terminator: u8) -> Self {
+        self.terminator = Some(terminator);
+        self
+    }
+
+    /// Returns the byte that escapes a quote in a quoted field, or `None` if
+    /// a quote is escaped only by doubling it.
+    #[must_use]
+    pub fn escape(&self) -> Option<u8> {
+        self.escape
+    }
+
+    /// Sets the byte that escapes a quote in a quoted field, such as `\`, in
+    /// addition to doubling the quote.
+    #[must_use]
+    pub fn with_escape(mut self, escape: u8) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// Returns the default `Config` with the delimiter that appears the most
+    /// in `line` outside double quotes, among comma, tab, pipe, and
+    /// semicolon. A tie goes to the earlier one in that order, and comma is
+    /// chosen if none appears.
+    #[must_use]
+    pub fn detect(line: &[u8]) -> Self {
+        let mut counts = [0_usize; DELIMITER_CANDIDATES.len()];
+        let mut quoted = false;
+        for &b in line {
+            if b == b'"' {
+                quoted = !quoted;
+            } else if !quoted {
+                if let Some(i) = DELIMITER_CANDIDATES.iter().position(|&d| d == b) {
+                    counts[i] += 1;
+                }
+            }
+        }
+        let mut best = 0;
+        for (i, &count) in counts.iter().enumerate().skip(1) {
+            if count > counts[best] {
+                best = i;
+            }
+        }
+        Config {
+            delimiter: DELIMITER_CANDIDATES[best],
+            ..Config::default()
+        }
+    }
+}
+/// What to do with a numeric field that cannot be parsed. Empty fields
+/// become the default value under any policy.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OnParseError {
+    /// Stores the default value of the type, e.g., 0.
+    #[default]
+    Default,
+    /// Stores a null.
+    Null,
+    /// Fails to build the batch.
+    Error,
+}
+
+/// The value stored for an empty field, or a field that cannot be parsed
+/// under [`OnParseError::Default`], in place of the default value of the
+/// type. The variant must match the parser of the column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DefaultValue {
+    /// For `FieldParser::Int64` and `FieldParser::Timestamp`.
+    Int64(i64),
+    /// For `FieldParser::Int32`.
+    Int32(i32),
+    /// For `FieldParser::Int16`.
+    Int16(i16),
+    /// For `FieldParser::Int8`.
+    Int8(i8),
+    /// For `FieldParser::Float64`.
+    Float64(f64),
+    /// For `FieldParser::Float32`.
+    Float32(f32),
+    /// For `FieldParser::UInt32`.
+    UInt32(u32),
+    /// For `FieldParser::UInt64`.
+    UInt64(u64),
+    /// For `FieldParser::Boolean`.
+    Boolean(bool),
+}
+
+impl DefaultValue {
+    /// Converts an `Int64` value for a column promoted to `Float64`.
+    fn promoted(self) -> Self {
+        match self {
+            #[allow(clippy::cast_precision_loss)] // 52-bit precision is good enough
+            Self::Int64(v) => Self::Float64(v as f64),
+            v => v,
+        }
+    }
+}
+
+/// A numeric field that could not be parsed, reported by
+/// [`Reader::next_batch_with_failures`].
+#[derive(Debug)]
+pub struct ParseFailure {
+    /// The index of the record in the batch.
+    pub row: usize,
+    /// The index of the field in the record.
+    pub column: usize,
+    /// The reason the field could not be parsed.
+    pub error: ParseError,
 }
+
+/// The default number of fields in a batch, i.e., the number of records times
+/// the number of parsers, from which `Reader` parses columns in parallel.
+pub const DEFAULT_PARALLELISM_THRESHOLD: usize = 1 << 16;
+
 /// CSV reader
 pub struct Reader<'a, I>
 where
@@ -306,6 +992,14 @@ where
     batch_size: usize,
     parsers: &'a [FieldParser],
     builder: csv_core::ReaderBuilder,
+    parallelism_threshold: usize,
+    expected_rows: Option<usize>,
+    extra_fields_tolerance: Option<usize>,
+    on_parse_error: OnParseError,
+    default_values: Vec<Option<DefaultValue>>,
+    multi_byte_delimiter: Option<Vec<u8>>,
+    float_promotion: bool,
+    promoted: Vec<usize>,
 }
 
 impl<'a, I> Reader<'a, I>
@@ -319,6 +1013,14 @@ where
             batch_size,
             parsers,
             builder: csv_core::ReaderBuilder::new(),
+            parallelism_threshold: DEFAULT_PARALLELISM_THRESHOLD,
+            expected_rows: None,
+            extra_fields_tolerance: None,
+            on_parse_error: OnParseError::Default,
+            default_values: Vec::new(),
+            multi_byte_delimiter: None,
+            float_promotion: false,
+            promoted: Vec::new(),
         }
     }
 
@@ -329,25 +1031,144 @@ where
         parsers: &'a [FieldParser],
     ) -> Self {
         Reader {
-            record_iter,
-            batch_size,
-            parsers,
             builder: config.into(),
+            ..Self::new(record_iter, batch_size, parsers)
+        }
+    }
+
+    /// Sets the number of fields in a batch, i.e., the number of records
+    /// times the number of parsers, from which columns are parsed in
+    /// parallel. Smaller batches are parsed sequentially to avoid the
+    /// overhead of spawning threads. The default is
+    /// [`DEFAULT_PARALLELISM_THRESHOLD`].
+    #[must_use]
+    pub fn with_parallelism_threshold(mut self, threshold: usize) -> Self {
+        self.parallelism_threshold = threshold;
+        self
+    }
+
+    /// Sets the estimated number of records to read in total. Each batch
+    /// reserves memory for the records expected to remain, instead of
+    /// `batch_size` records, so that a small input does not reserve a whole
+    /// batch. The estimate only affects memory allocation; all the records
+    /// are read even if it is too small or too large. Once more records than
+    /// estimated have been read, each batch reserves `batch_size` records
+    /// again.
+    #[must_use]
+    pub fn with_expected_rows(mut self, expected_rows: usize) -> Self {
+        self.expected_rows = Some(expected_rows);
+        self
+    }
+
+    /// Makes `next_batch` fail on a record with more than `tolerance` fields
+    /// beyond the number of parsers, instead of ignoring the extra fields.
+    #[must_use]
+    pub fn with_strict_field_count(mut self, tolerance: usize) -> Self {
+        self.extra_fields_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Sets what to do with a numeric field that cannot be parsed. The
+    /// default is [`OnParseError::Default`].
+    #[must_use]
+    pub fn with_on_parse_error(mut self, policy: OnParseError) -> Self {
+        self.on_parse_error = policy;
+        self
+    }
+
+    /// Sets the value stored in column `column` for an empty field, or a
+    /// field that cannot be parsed under [`OnParseError::Default`]. Reading
+    /// a batch fails if `value` does not match the parser of the column.
+    #[must_use]
+    pub fn with_default_value(mut self, column: usize, value: DefaultValue) -> Self {
+        if self.default_values.len() <= column {
+            self.default_values.resize(column + 1, None);
+        }
+        self.default_values[column] = Some(value);
+        self
+    }
+
+    /// Splits each record on `delimiter`, which may be longer than one byte,
+    /// instead of parsing it as CSV. Quotes are not recognized in this mode;
+    /// see [`Record::split`].
+    #[must_use]
+    pub fn with_multi_byte_delimiter(mut self, delimiter: &[u8]) -> Self {
+        self.multi_byte_delimiter = Some(delimiter.to_vec());
+        self
+    }
+
+    /// Parses an `Int64` column as `Float64` once it has a value that is not
+    /// an integer but a finite floating-point number, e.g., `1.5`, instead of
+    /// failing to parse the value. The whole column of the batch is parsed
+    /// again, and the column stays `Float64` in later batches; see
+    /// [`Reader::promoted_columns`]. A value that the `Int64` parser accepts
+    /// is still parsed by it and converted to `f64`, and so is a default
+    /// value set by [`Reader::with_default_value`]. Non-finite values, such as
+    /// `inf` or `NaN`, fail to parse.
+    #[must_use]
+    pub fn with_float_promotion(mut self) -> Self {
+        self.float_promotion = true;
+        self
+    }
+
+    /// Returns the indices of the columns promoted from `Int64` to `Float64`
+    /// so far, in the order of promotion. The batches read before a column
+    /// was promoted keep its `Int64` array; a column built from them can be
+    /// promoted with [`Column::promote_to_float64`] before appending the later
+    /// batches to it.
+    ///
+    /// [`Column::promote_to_float64`]: crate::Column::promote_to_float64
+    #[must_use]
+    pub fn promoted_columns(&self) -> &[usize] {
+        &self.promoted
+    }
+
+    /// Returns the number of records to reserve memory for in the next
+    /// batch.
+    fn batch_capacity(&self) -> usize {
+        match self.expected_rows {
+            Some(remaining) if remaining > 0 => remaining.min(self.batch_size),
+            _ => self.batch_size,
         }
     }
 
+    /// Returns `true` if a batch of `num_records` records is parsed in
+    /// parallel.
+    fn parses_in_parallel(&self, num_records: usize) -> bool {
+        self.parsers.len() > 1
+            && num_records.saturating_mul(self.parsers.len()) >= self.parallelism_threshold
+    }
+
     /// Reads the next batch of records.
     ///
     /// # Errors
     ///
     /// Returns an error of parsing a field fails.
     pub fn next_batch(&mut self) -> Result<Option<record::Batch>, arrow::error::ArrowError> {
-        let mut rows = Vec::with_capacity(self.batch_size);
+        Ok(self.next_batch_with_failures()?.map(|(batch, _)| batch))
+    }
+
+    /// Reads the next batch of records, along with every numeric field that
+    /// could not be parsed, ordered by column and then by row. The values
+    /// stored for those fields follow [`Reader::with_on_parse_error`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if parsing a field fails under
+    /// [`OnParseError::Error`], or a string field is not valid UTF-8.
+    pub fn next_batch_with_failures(
+        &mut self,
+    ) -> Result<Option<(record::Batch, Vec<ParseFailure>)>, ArrowError> {
+        let mut rows = Vec::with_capacity(self.batch_capacity());
         let mut csv_reader = self.builder.build();
         for _ in 0..self.batch_size {
             match self.record_iter.next() {
                 Some(r) => {
-                    if let Some(r) = Record::new(&mut csv_reader, r) {
+                    let record = match &self.multi_byte_delimiter {
+                        Some(delimiter) => Record::split(r, delimiter),
+                        None => Record::new(&mut csv_reader, r),
+                    };
+                    if let Some(r) = record {
                         rows.push(r);
                     }
                     // Skip invalid rows.
@@ -355,44 +1176,92 @@ where
                 None => break,
             }
         }
+        self.parse_records(&rows)
+    }
+
+    /// Reads the next batch of records from `input` instead of the byte
+    /// sequences the reader was created with, like [`Reader::next_batch`]. A
+    /// quoted field may contain line breaks, as records are read by the CSV
+    /// parser directly from `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from `input` or parsing a field fails.
+    pub(crate) fn next_batch_from_buf(
+        &mut self,
+        input: &mut dyn BufRead,
+    ) -> Result<Option<record::Batch>, ArrowError> {
+        let mut rows = Vec::with_capacity(self.batch_capacity());
+        let mut csv_reader = self.builder.build();
+        while rows.len() < self.batch_size {
+            match Record::from_buf(&mut csv_reader, input)? {
+                Some(record) => rows.push(record),
+                None => break,
+            }
+        }
+        Ok(self.parse_records(&rows)?.map(|(batch, _)| batch))
+    }
 
+    /// Builds a batch from `rows`, or returns `None` if there is no record.
+    fn parse_records(
+        &mut self,
+        rows: &[Record],
+    ) -> Result<Option<(record::Batch, Vec<ParseFailure>)>, ArrowError> {
         if rows.is_empty() {
             return Ok(None);
         }
+        if let Some(tolerance) = self.extra_fields_tolerance {
+            let max_fields = self.parsers.len().saturating_add(tolerance);
+            if let Some((i, row)) = rows
+                .iter()
+                .enumerate()
+                .find(|(_, row)| row.ends.len() > max_fields)
+            {
+                return Err(ArrowError::ParseError(format!(
+                    "record {i} of the batch has {} fields, more than {max_fields}",
+                    row.ends.len()
+                )));
+            }
+        }
+        if let Some(expected_rows) = self.expected_rows.as_mut() {
+            *expected_rows = expected_rows.saturating_sub(rows.len());
+        }
 
-        let mut arrays = Vec::with_capacity(self.parsers.len());
-        for (i, parser) in self.parsers.iter().enumerate() {
-            let col = match parser {
-                FieldParser::Int64(parse) | FieldParser::Timestamp(parse) => {
-                    build_primitive_array::<Int64Type, Int64Parser>(&rows, i, parse)
-                }
-                FieldParser::Float64(parse) => {
-                    build_primitive_array::<Float64Type, Float64Parser>(&rows, i, parse)
-                }
-                FieldParser::Utf8 => {
-                    let mut builder = StringBuilder::new();
-                    for row in &rows {
-                        builder.append_value(
-                            std::str::from_utf8(row.get(i).unwrap_or_default())
-                                .map_err(|e| ArrowError::ParseError(e.to_string()))?,
-                        );
-                    }
-                    Arc::new(builder.finish())
-                }
-                FieldParser::Binary => {
-                    let mut builder = BinaryBuilder::new();
-                    for row in &rows {
-                        builder.append_value(row.get(i).unwrap_or_default());
+        let parallel = self.parses_in_parallel(rows.len());
+        let promoted_parsers: Vec<FieldParser>;
+        let parsers = if self.promoted.is_empty() {
+            self.parsers
+        } else {
+            promoted_parsers = self
+                .parsers
+                .iter()
+                .enumerate()
+                .map(|(i, parser)| match parser {
+                    FieldParser::Int64(parse_int) if self.promoted.contains(&i) => {
+                        promoted_parser(parse_int)
                     }
-                    Arc::new(builder.finish())
-                }
-                FieldParser::UInt32(parse) => {
-                    build_primitive_array::<UInt32Type, UInt32Parser>(&rows, i, parse)
+                    _ => parser.clone(),
+                })
+                .collect();
+            &promoted_parsers
+        };
+        let (arrays, failures) = records_to_columns(
+            rows,
+            parsers,
+            parallel,
+            self.on_parse_error,
+            &self.default_values,
+            self.float_promotion,
+        )?;
+        for (i, (parser, array)) in parsers.iter().zip(&arrays).enumerate() {
+            if matches!(parser, FieldParser::Int64(_)) && array.data_type() == &DataType::Float64 {
+                self.promoted.push(i);
+                if let Some(Some(value)) = self.default_values.get_mut(i) {
+                    *value = value.promoted();
                 }
-            };
-            arrays.push(col);
+            }
         }
-        Ok(Some(record::Batch::new(arrays)))
+        Ok(Some((record::Batch::new(arrays), failures)))
     }
 
     pub fn generate_empty_batch(&self) -> record::Batch {
@@ -404,14 +1273,28 @@ where
                     FieldParser::Int64(_) | FieldParser::Timestamp(_) => {
                         Arc::new(PrimitiveBuilder::<Int64Type>::new().finish())
                     }
+                    FieldParser::Int32(_) => {
+                        Arc::new(PrimitiveBuilder::<Int32Type>::new().finish())
+                    }
+                    FieldParser::Int16(_) => {
+                        Arc::new(PrimitiveBuilder::<Int16Type>::new().finish())
+                    }
+                    FieldParser::Int8(_) => Arc::new(PrimitiveBuilder::<Int8Type>::new().finish()),
                     FieldParser::Float64(_) => {
                         Arc::new(PrimitiveBuilder::<Float64Type>::new().finish())
                     }
+                    FieldParser::Float32(_) => {
+                        Arc::new(PrimitiveBuilder::<Float32Type>::new().finish())
+                    }
                     FieldParser::Utf8 => Arc::new(StringBuilder::new().finish()),
                     FieldParser::Binary => Arc::new(BinaryBuilder::new().finish()),
+                    FieldParser::Boolean(_) => Arc::new(BooleanBuilder::new().finish()),
                     FieldParser::UInt32(_) => {
                         Arc::new(PrimitiveBuilder::<UInt32Type>::new().finish())
                     }
+                    FieldParser::UInt64(_) => {
+                        Arc::new(PrimitiveBuilder::<UInt64Type>::new().finish())
+                    }
                 }
             })
             .collect();
@@ -419,37 +1302,326 @@ where
     }
 }
 
-fn build_primitive_array<T, P>(rows: &[Record], col_idx: usize, parse: &Arc<P>) -> Arc<dyn Array>
+/// The arrays built from a batch of records, and the fields that could not be
+/// parsed.
+type ParsedColumns = (Vec<Arc<dyn Array>>, Vec<ParseFailure>);
+
+/// Builds one array for each parser from the fields of `rows`, and collects
+/// the fields that cannot be parsed. If `parallel` is `true`, the columns are
+/// built by multiple threads. If `promote` is `true`, an `Int64` column with a
+/// floating-point number is built as `Float64`.
+fn records_to_columns(
+    rows: &[Record],
+    parsers: &[FieldParser],
+    parallel: bool,
+    on_error: OnParseError,
+    default_values: &[Option<DefaultValue>],
+    promote: bool,
+) -> Result<ParsedColumns, ArrowError> {
+    let default_value = |i: usize| default_values.get(i).copied().flatten();
+    let build = |parsers: &[FieldParser], offset: usize| {
+        let mut failures = Vec::new();
+        let arrays = parsers
+            .iter()
+            .enumerate()
+            .map(|(i, parser)| {
+                let i = offset + i;
+                build_column(
+                    rows,
+                    i,
+                    parser,
+                    on_error,
+                    default_value(i),
+                    promote,
+                    &mut failures,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((arrays, failures))
+    };
+    if !parallel {
+        return build(parsers, 0);
+    }
+
+    let num_threads = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
+        .min(parsers.len())
+        .max(1);
+    let chunk_size = ((parsers.len() + num_threads - 1) / num_threads).max(1);
+    std::thread::scope(|s| {
+        let handles: Vec<_> = parsers
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk, parsers)| s.spawn(move || build(parsers, chunk * chunk_size)))
+            .collect();
+        let mut arrays = Vec::with_capacity(parsers.len());
+        let mut failures = Vec::new();
+        for handle in handles {
+            let (a, f) = handle
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e))?;
+            arrays.extend(a);
+            failures.extend(f);
+        }
+        Ok((arrays, failures))
+    })
+}
+
+/// Builds an array from the `i`-th fields of `rows`, appending the fields
+/// that cannot be parsed to `failures`. If `promote` is `true` and `parser` is
+/// `FieldParser::Int64`, the fields are parsed as `f64` if any of them is a
+/// finite floating-point number.
+fn build_column(
+    rows: &[Record],
+    i: usize,
+    parser: &FieldParser,
+    on_error: OnParseError,
+    default_value: Option<DefaultValue>,
+    promote: bool,
+    failures: &mut Vec<ParseFailure>,
+) -> Result<Arc<dyn Array>, ArrowError> {
+    let float64;
+    let (parser, default_value) = match parser {
+        FieldParser::Int64(parse_int) if promote && has_float(rows, i, parse_int.as_ref()) => {
+            float64 = promoted_parser(parse_int);
+            (&float64, default_value.map(DefaultValue::promoted))
+        }
+        _ => (parser, default_value),
+    };
+    let mismatch = || {
+        ArrowError::InvalidArgumentError(format!(
+            "default value {default_value:?} does not match the parser of field {i}"
+        ))
+    };
+    // Builds a primitive array with the default value of `$variant`.
+    macro_rules! primitive_array {
+        ($t:ty, $parse:expr, $variant:ident) => {{
+            let default = match default_value {
+                None => Default::default(),
+                Some(DefaultValue::$variant(default)) => default,
+                Some(_) => return Err(mismatch()),
+            };
+            build_array(
+                PrimitiveBuilder::<$t>::with_capacity(rows.len()),
+                rows,
+                i,
+                $parse.as_ref(),
+                on_error,
+                default,
+                failures,
+            )?
+        }};
+    }
+    let col: Arc<dyn Array> = match (parser, default_value) {
+        (FieldParser::Int64(parse) | FieldParser::Timestamp(parse), _) => {
+            primitive_array!(Int64Type, parse, Int64)
+        }
+        (FieldParser::Int32(parse), _) => primitive_array!(Int32Type, parse, Int32),
+        (FieldParser::Int16(parse), _) => primitive_array!(Int16Type, parse, Int16),
+        (FieldParser::Int8(parse), _) => primitive_array!(Int8Type, parse, Int8),
+        (FieldParser::Float64(parse), _) => {
+            primitive_array!(Float64Type, parse, Float64)
+        }
+        (FieldParser::Float32(parse), _) => {
+            primitive_array!(Float32Type, parse, Float32)
+        }
+        (FieldParser::UInt32(parse), _) => {
+            primitive_array!(UInt32Type, parse, UInt32)
+        }
+        (FieldParser::UInt64(parse), _) => {
+            primitive_array!(UInt64Type, parse, UInt64)
+        }
+        (FieldParser::Boolean(parse), _) => {
+            let default = match default_value {
+                None => false,
+                Some(DefaultValue::Boolean(default)) => default,
+                Some(_) => return Err(mismatch()),
+            };
+            build_array(
+                BooleanBuilder::with_capacity(rows.len()),
+                rows,
+                i,
+                parse.as_ref(),
+                on_error,
+                default,
+                failures,
+            )?
+        }
+        (FieldParser::Utf8, None) => {
+            let mut builder = StringBuilder::with_capacity(rows.len(), field_bytes(rows, i));
+            for (row_index, row) in rows.iter().enumerate() {
+                let value = std::str::from_utf8(row.get(i).unwrap_or_default()).map_err(|e| {
+                    ArrowError::ParseError(format!(
+                        "field {i} of record {row_index} of the batch: {e}"
+                    ))
+                })?;
+                builder.append_value(value);
+            }
+            Arc::new(builder.finish())
+        }
+        (FieldParser::Binary, None) => {
+            let mut builder = BinaryBuilder::with_capacity(rows.len(), field_bytes(rows, i));
+            for row in rows {
+                builder.append_value(row.get(i).unwrap_or_default());
+            }
+            Arc::new(builder.finish())
+        }
+        (_, Some(_)) => return Err(mismatch()),
+    };
+    Ok(col)
+}
+
+/// Returns `true` if any of the `i`-th fields of `rows` cannot be parsed by
+/// `parse_int` but is a finite floating-point number.
+fn has_float(rows: &[Record], i: usize, parse_int: &Int64Parser) -> bool {
+    rows.iter()
+        .filter_map(|row| row.get(i))
+        .any(|s| !s.is_empty() && parse_int(s).is_err() && parse_finite::<f64>(s).is_ok())
+}
+
+/// Creates the `f64` parser of an `Int64` column promoted to `Float64`, which
+/// converts the values accepted by `parse_int` and parses the others as finite
+/// floating-point numbers.
+fn promoted_parser(parse_int: &Arc<Int64Parser>) -> FieldParser {
+    let parse_int = parse_int.clone();
+    FieldParser::Float64(Arc::new(move |v| match parse_int(v) {
+        #[allow(clippy::cast_precision_loss)] // 52-bit precision is good enough
+        Ok(v) => Ok(v as f64),
+        Err(_) => parse_finite::<f64>(v),
+    }))
+}
+
+/// Returns the total length of the `i`-th fields of `rows`.
+fn field_bytes(rows: &[Record], i: usize) -> usize {
+    rows.iter()
+        .map(|row| row.get(i).map_or(0, <[u8]>::len))
+        .sum()
+}
+
+/// An array builder to which [`build_array`] appends parsed values.
+trait ValueBuilder: ArrayBuilder {
+    type Value: Copy;
+
+    fn append_value(&mut self, value: Self::Value);
+    fn append_null(&mut self);
+}
+
+impl<T: ArrowPrimitiveType> ValueBuilder for PrimitiveBuilder<T> {
+    type Value = T::Native;
+
+    fn append_value(&mut self, value: Self::Value) {
+        PrimitiveBuilder::append_value(self, value);
+    }
+
+    fn append_null(&mut self) {
+        PrimitiveBuilder::append_null(self);
+    }
+}
+
+impl ValueBuilder for BooleanBuilder {
+    type Value = bool;
+
+    fn append_value(&mut self, value: Self::Value) {
+        BooleanBuilder::append_value(self, value);
+    }
+
+    fn append_null(&mut self) {
+        BooleanBuilder::append_null(self);
+    }
+}
+
+/// Builds an array with `builder` from the `col_idx`-th fields of `rows`,
+/// handling fields that cannot be parsed according to `on_error`. Empty
+/// fields, and fields that cannot be parsed under `OnParseError::Default`,
+/// become `default`. Unless `on_error` is `OnParseError::Error`, the fields
+/// that cannot be parsed are appended to `failures`.
+fn build_array<B, P>(
+    mut builder: B,
+    rows: &[Record],
+    col_idx: usize,
+    parse: &P,
+    on_error: OnParseError,
+    default: B::Value,
+    failures: &mut Vec<ParseFailure>,
+) -> Result<Arc<dyn Array>, ArrowError>
 where
-    T: ArrowPrimitiveType,
-    T::Native: Default,
-    P: Fn(&[u8]) -> Result<T::Native, ParseError> + Send + Sync + ?Sized,
+    B: ValueBuilder,
+    P: Fn(&[u8]) -> Result<B::Value, ParseError> + ?Sized,
 {
-    let mut builder = PrimitiveBuilder::<T>::new();
-    for row in rows {
+    for (row_index, row) in rows.iter().enumerate() {
         match row.get(col_idx) {
-            Some(s) if !s.is_empty() => {
-                let t = parse(s).unwrap_or_default();
-                builder.append_value(t);
-            }
-            _ => builder.append_value(T::Native::default()),
+            Some(s) if !s.is_empty() => match parse(s) {
+                Ok(t) => builder.append_value(t),
+                Err(e) => {
+                    match on_error {
+                        OnParseError::Default => builder.append_value(default),
+                        OnParseError::Null => builder.append_null(),
+                        OnParseError::Error => {
+                            return Err(ArrowError::ParseError(format!(
+                                "field {col_idx}: {e:?}: {}",
+                                String::from_utf8_lossy(s)
+                            )))
+                        }
+                    }
+                    failures.push(ParseFailure {
+                        row: row_index,
+                        column: col_idx,
+                        error: e,
+                    });
+                }
+            },
+            _ => builder.append_value(default),
         }
     }
-    Arc::new(builder.finish())
+    Ok(builder.finish())
 }
 
-/// Infers the data type of a field in a CSV record.
-fn infer_field_type(field: &[u8]) -> DataType {
-    if let Ok(s) = str::from_utf8(field) {
-        if s.parse::<i64>().is_ok() {
-            DataType::Int64
+/// The type inferred for a field so far, ordered from the narrowest to the
+/// widest type.
+#[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+enum FieldInference {
+    #[default]
+    Unknown,
+    Int64,
+    Float64,
+    Utf8,
+    Binary,
+}
+
+impl FieldInference {
+    /// Widens the inferred type, if necessary, to accommodate `field`.
+    ///
+    /// A field that has seen a byte sequence that is not valid UTF-8 stays
+    /// `Binary`, and later values are not examined at all. Likewise, a `Utf8`
+    /// field only checks the UTF-8 validity of later values.
+    fn update(&mut self, field: &[u8]) {
+        if *self == Self::Binary {
+            return;
+        }
+        let Ok(s) = str::from_utf8(field) else {
+            *self = Self::Binary;
+            return;
+        };
+        if *self == Self::Utf8 {
+            return;
+        }
+        let observed = if s.parse::<i64>().is_ok() {
+            Self::Int64
         } else if s.parse::<f64>().is_ok() {
-            DataType::Float64
+            Self::Float64
         } else {
-            DataType::Utf8
+            Self::Utf8
+        };
+        *self = (*self).max(observed);
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Int64 => DataType::Int64,
+            Self::Float64 => DataType::Float64,
+            Self::Unknown | Self::Utf8 => DataType::Utf8,
+            Self::Binary => DataType::Binary,
         }
-    } else {
-        DataType::Binary
     }
 }
 
@@ -459,21 +1631,134 @@ fn infer_field_type(field: &[u8]) -> DataType {
 ///
 /// Returns an error if there is no data to read from `reader`.
 pub fn infer_schema<R: Read>(reader: &mut BufReader<R>) -> Result<Schema, String> {
-    let mut csv_reader = csv_core::Reader::new();
-    let record = Record::from_buf(&mut csv_reader, reader).ok_or("no data available")?;
-    let mut fields = Vec::new();
-    for i in 0..record.ends.len() {
-        let data_type = record.get(i).map_or(DataType::Utf8, infer_field_type);
-        fields.push(Field::new("", data_type, false));
+    infer_schema_with_config(reader, Config::default())
+}
+
+/// Infers the schema of CSV in the format of `config` by reading one record.
+///
+/// # Errors
+///
+/// Returns an error if there is no data to read from `reader`, or reading
+/// from it fails.
+pub fn infer_schema_with_config<R: Read>(
+    reader: &mut BufReader<R>,
+    config: Config,
+) -> Result<Schema, String> {
+    let mut csv_reader = csv_core::ReaderBuilder::from(config).build();
+    let record = Record::from_buf(&mut csv_reader, reader)
+        .map_err(|e| e.to_string())?
+        .ok_or("no data available")?;
+    let fields = record
+        .fields()
+        .into_iter()
+        .map(|field| Field::new("", field_type(field), false))
+        .collect::<Vec<_>>();
+    Ok(Schema::new(fields))
+}
+
+/// Infers the schema of CSV in the format of `config` from up to
+/// `sample_rows` records, widening the
+/// type of each field as needed, from `Int64` to `Float64` to `Utf8`, or to
+/// `Binary` for a value that is not valid UTF-8. Empty fields do not affect
+/// the type, and a field that is empty in every record is `Utf8`. Returns the
+/// schema and the number of records read.
+///
+/// # Errors
+///
+/// Returns an error if `sample_rows` is zero, there is no data to read from
+/// `reader`, or reading from it fails.
+pub fn infer_schema_sampled<R: Read>(
+    reader: &mut BufReader<R>,
+    config: Config,
+    sample_rows: usize,
+) -> Result<(Schema, usize), String> {
+    if sample_rows == 0 {
+        return Err("sample_rows must be positive".to_string());
+    }
+    let mut csv_reader = csv_core::ReaderBuilder::from(config).build();
+    let mut inferences = Vec::new();
+    let mut sampled = 0;
+    while sampled < sample_rows {
+        let Some(record) = Record::from_buf(&mut csv_reader, reader).map_err(|e| e.to_string())?
+        else {
+            break;
+        };
+        let fields = record.fields();
+        if inferences.len() < fields.len() {
+            inferences.resize(fields.len(), FieldInference::default());
+        }
+        for (inference, field) in inferences.iter_mut().zip(fields) {
+            if !field.is_empty() {
+                inference.update(field);
+            }
+        }
+        sampled += 1;
+    }
+    if sampled == 0 {
+        return Err("no data available".to_string());
+    }
+    let fields = inferences
+        .into_iter()
+        .map(|inference| Field::new("", inference.data_type(), false))
+        .collect::<Vec<_>>();
+    Ok((Schema::new(fields), sampled))
+}
+
+/// Infers the schema of CSV in the format of `config` whose first record is a
+/// header. The fields of the header become the names of the fields, and their
+/// types are inferred from the second record.
+///
+/// # Errors
+///
+/// Returns an error if `reader` has fewer than two records, reading from it
+/// fails, the header is not valid UTF-8, or the header and the second record
+/// have different numbers of fields.
+pub fn infer_schema_with_header<R: Read>(
+    reader: &mut BufReader<R>,
+    config: Config,
+) -> Result<Schema, String> {
+    let mut csv_reader = csv_core::ReaderBuilder::from(config).build();
+    let header = Record::from_buf(&mut csv_reader, reader)
+        .map_err(|e| e.to_string())?
+        .ok_or("no header available")?;
+    let record = Record::from_buf(&mut csv_reader, reader)
+        .map_err(|e| e.to_string())?
+        .ok_or("no data available")?;
+    let (names, values) = (header.fields(), record.fields());
+    if names.len() != values.len() {
+        return Err(format!(
+            "header has {} fields, but the first record has {}",
+            names.len(),
+            values.len()
+        ));
     }
+    let fields = names
+        .into_iter()
+        .zip(values)
+        .enumerate()
+        .map(|(i, (name, value))| {
+            let name = str::from_utf8(name).map_err(|e| format!("field {i} of the header: {e}"))?;
+            Ok(Field::new(name, field_type(value), false))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
     Ok(Schema::new(fields))
 }
 
+/// Returns the type inferred from a single value of a field.
+fn field_type(field: &[u8]) -> DataType {
+    let mut inference = FieldInference::default();
+    inference.update(field);
+    inference.data_type()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::table::Column;
-    use arrow::array::{Array, BinaryArray, StringArray};
+    use arrow::array::{
+        Array, BinaryArray, BooleanArray, Float32Array, Float64Array, Int64Array, StringArray,
+        UInt64Array,
+    };
     use chrono::{NaiveDate, NaiveDateTime};
     use itertools::izip;
     use serde_test::{assert_tokens, Token};
@@ -484,7 +1769,7 @@ mod tests {
         let c0_v: Vec<i64> = vec![1, 3, 3, 5, 2, 1, 3];
         let c1_v: Vec<_> = vec!["111a qwer", "b", "c", "d", "b", "111a qwer", "111a qwer"];
         let c2_v: Vec<Ipv4Addr> = vec![
-            Ipv4Addr::new(127, 0, 0, 1),
+            Ipv4Addr::LOCALHOST,
             Ipv4Addr::new(127, 0, 0, 2),
             Ipv4Addr::new(127, 0, 0, 3),
             Ipv4Addr::new(127, 0, 0, 4),
@@ -621,6 +1906,649 @@ mod tests {
             .all(|(a, b)| a.data_type() == b.data_type()));
     }
 
+    #[test]
+    fn tab_delimited_inference() {
+        let buf = "Cat\t50\t1.0,5\n".as_bytes();
+        let schema = infer_schema(&mut BufReader::new(buf)).unwrap();
+        assert_eq!(schema.fields().len(), 2);
+
+        let config = Config::detect(buf);
+        assert_eq!(config.delimiter(), b'\t');
+        let schema = infer_schema_with_config(&mut BufReader::new(buf), config).unwrap();
+        assert_eq!(
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>(),
+            vec![DataType::Utf8, DataType::Int64, DataType::Utf8]
+        );
+
+        assert_eq!(Config::detect(b"a|b|\"c;d;e\"").delimiter(), b'|');
+        assert_eq!(Config::detect(b"a;b,c").delimiter(), b',');
+        assert_eq!(Config::detect(b"abc"), Config::default());
+    }
+
+    #[test]
+    fn boolean_field() {
+        let parsers = [
+            FieldParser::boolean(),
+            FieldParser::boolean_with_tokens(&["Yes", "Y"], &["No", "N"]),
+        ];
+        let data = [
+            &b"TRUE,yes"[..],
+            b"False,n",
+            b"1,Y",
+            b"0,no",
+            b"maybe,true",
+            b",",
+        ];
+        let (batch, failures) = Reader::new(data.iter().copied(), 10, &parsers)
+            .next_batch_with_failures()
+            .unwrap()
+            .unwrap();
+        let values = |i: usize| {
+            batch.columns()[i]
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>()
+        };
+        let expected = [true, false, true, false, false, false].map(Some);
+        assert_eq!(values(0), expected);
+        assert_eq!(values(1), expected);
+        let failures = failures
+            .iter()
+            .map(|f| (f.row, f.column))
+            .collect::<Vec<_>>();
+        assert_eq!(failures, [(4, 0), (4, 1)]);
+
+        let batch = Reader::new(data.iter().copied(), 10, &parsers)
+            .with_on_parse_error(OnParseError::Null)
+            .with_default_value(0, DefaultValue::Boolean(true))
+            .next_batch()
+            .unwrap()
+            .unwrap();
+        let column = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(column.is_null(4));
+        assert!(column.value(5));
+    }
+
+    #[test]
+    fn float32_field() {
+        let parsers = [FieldParser::float32()];
+        let data = [&b"1.5e-3"[..], b"-2.25", b"3E2", b"x"];
+        let (batch, failures) = Reader::new(data.iter().copied(), 10, &parsers)
+            .next_batch_with_failures()
+            .unwrap()
+            .unwrap();
+        let column = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float32Array>()
+            .unwrap();
+        assert_eq!(column.values().as_ref(), [1.5e-3, -2.25, 300.0, 0.0]);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].row, 3);
+
+        let c1: Column = (Arc::new(column.clone()) as Arc<dyn Array>).into();
+        let c2 = Column::try_from_slice::<Float32Type>(&[1.5e-3, -2.25, 300.0, 0.0]).unwrap();
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn finite_float_fields() {
+        let FieldParser::Float64(parse) = FieldParser::finite_float64() else {
+            panic!("finite_float64 should produce f64");
+        };
+        assert_eq!(parse(b"-0.0").unwrap().to_bits(), (-0.0_f64).to_bits());
+        assert_eq!(parse(b"+1.5").unwrap().to_bits(), 1.5_f64.to_bits());
+        for v in [&b"inf"[..], b"-inf", b"infinity", b"nan", b"NaN"] {
+            assert!(parse(v).is_err());
+        }
+        let FieldParser::Float32(parse) = FieldParser::finite_float32() else {
+            panic!("finite_float32 should produce f32");
+        };
+        assert!(parse(b"-0.0").unwrap().is_sign_negative());
+        assert!(parse(b"nan").is_err());
+
+        let parsers = [FieldParser::finite_float64()];
+        let data = [&b"-0.0"[..], b"inf", b"nan", b"2.5"];
+        let read = |policy| {
+            Reader::new(data.iter().copied(), 10, &parsers)
+                .with_on_parse_error(policy)
+                .next_batch_with_failures()
+        };
+        let (batch, failures) = read(OnParseError::Null).unwrap().unwrap();
+        let column = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(
+            column.iter().collect::<Vec<_>>(),
+            [Some(-0.0), None, None, Some(2.5)]
+        );
+        assert_eq!(failures.iter().map(|f| f.row).collect::<Vec<_>>(), [1, 2]);
+        assert!(read(OnParseError::Error).is_err());
+    }
+
+    #[test]
+    fn narrow_int_fields() {
+        let parsers = [
+            FieldParser::int32(),
+            FieldParser::int16(),
+            FieldParser::int8(),
+        ];
+        let data = [&b"-2147483648,-32768,-128"[..], b"2147483647,32767,127"];
+        let batch = Reader::new(data.iter().copied(), 10, &parsers)
+            .next_batch()
+            .unwrap()
+            .unwrap();
+        let values = |i: usize| {
+            let column = arrow::compute::cast(&batch.columns()[i], &DataType::Int64).unwrap();
+            let column = column.as_any().downcast_ref::<Int64Array>().unwrap();
+            column.values().to_vec()
+        };
+        assert_eq!(
+            batch
+                .columns()
+                .iter()
+                .map(|c| c.data_type().clone())
+                .collect::<Vec<_>>(),
+            [DataType::Int32, DataType::Int16, DataType::Int8]
+        );
+        assert_eq!(values(0), [i64::from(i32::MIN), i64::from(i32::MAX)]);
+        assert_eq!(values(1), [i64::from(i16::MIN), i64::from(i16::MAX)]);
+        assert_eq!(values(2), [i64::from(i8::MIN), i64::from(i8::MAX)]);
+
+        let data = [&b"2147483648,0,0"[..], b"0,-32769,0", b"0,0,128"];
+        let (_, failures) = Reader::new(data.iter().copied(), 10, &parsers)
+            .next_batch_with_failures()
+            .unwrap()
+            .unwrap();
+        let failures = failures
+            .iter()
+            .map(|f| (f.row, f.column))
+            .collect::<Vec<_>>();
+        assert_eq!(failures, [(0, 0), (1, 1), (2, 2)]);
+        assert!(Reader::new(data.iter().copied(), 10, &parsers)
+            .with_on_parse_error(OnParseError::Error)
+            .next_batch()
+            .is_err());
+    }
+
+    #[test]
+    fn uint64_field() {
+        let parsers = [FieldParser::uint64()];
+        let values = [0, u64::from(u32::MAX) + 1, u64::MAX];
+        let data = values
+            .iter()
+            .map(|v| v.to_string().into_bytes())
+            .collect::<Vec<_>>();
+        let batch = Reader::new(data.iter().map(Vec::as_slice), 10, &parsers)
+            .next_batch()
+            .unwrap()
+            .unwrap();
+        let column = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(column.values().as_ref(), values);
+
+        let FieldParser::UInt64(parse) = FieldParser::uint64() else {
+            panic!("uint64 parser should produce u64");
+        };
+        assert!(parse(b"-1").is_err());
+        assert!(parse(b"18446744073709551616").is_err());
+    }
+
+    #[test]
+    fn sampled_inference() {
+        let buf = &b"1,,a,1\n2,3,b,x\n3,4.5,,\xff\n"[..];
+        let data_types = |schema: &Schema| {
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>()
+        };
+
+        let (schema, sampled) =
+            infer_schema_sampled(&mut BufReader::new(buf), Config::default(), 10).unwrap();
+        assert_eq!(sampled, 3);
+        assert_eq!(
+            data_types(&schema),
+            [
+                DataType::Int64,
+                DataType::Float64,
+                DataType::Utf8,
+                DataType::Binary
+            ]
+        );
+
+        let (schema, sampled) =
+            infer_schema_sampled(&mut BufReader::new(buf), Config::default(), 2).unwrap();
+        assert_eq!(sampled, 2);
+        assert_eq!(
+            data_types(&schema),
+            [
+                DataType::Int64,
+                DataType::Int64,
+                DataType::Utf8,
+                DataType::Utf8
+            ]
+        );
+
+        let (schema, _) =
+            infer_schema_sampled(&mut BufReader::new(&b"1,\n"[..]), Config::default(), 1).unwrap();
+        assert_eq!(data_types(&schema), [DataType::Int64, DataType::Utf8]);
+        assert!(
+            infer_schema_sampled(&mut BufReader::new(&b""[..]), Config::default(), 10).is_err()
+        );
+        assert!(infer_schema_sampled(&mut BufReader::new(buf), Config::default(), 0).is_err());
+
+        let config = Config::detect(b"1;a\n2.5;b\n");
+        let (schema, sampled) =
+            infer_schema_sampled(&mut BufReader::new(&b"1;a\n2.5;b\n"[..]), config, 10).unwrap();
+        assert_eq!(sampled, 2);
+        assert_eq!(data_types(&schema), [DataType::Float64, DataType::Utf8]);
+    }
+
+    #[test]
+    fn header_inference() {
+        let infer =
+            |buf: &[u8]| infer_schema_with_header(&mut BufReader::new(buf), Config::default());
+        let schema = infer(b"name,count,ratio\nCat,50,1.5\n").unwrap();
+        assert_eq!(
+            schema
+                .fields()
+                .iter()
+                .map(|f| (f.name().as_str(), f.data_type().clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("name", DataType::Utf8),
+                ("count", DataType::Int64),
+                ("ratio", DataType::Float64)
+            ]
+        );
+        assert_eq!(schema.index_of("count").unwrap(), 1);
+
+        assert!(infer(b"name,count\nCat,50,1.5\n").is_err());
+        assert!(infer(b"name,count\n").is_err());
+        assert!(infer(b"\xff,count\nCat,50\n").is_err());
+    }
+
+    #[test]
+    fn configured_delimiter() {
+        let config = Config::default().with_delimiter(b'|').with_quote(b'\'');
+        assert_eq!((config.delimiter(), config.quote()), (b'|', b'\''));
+
+        let mut reader = csv_core::ReaderBuilder::from(config).build();
+        let record = Record::new(&mut reader, b"a|'b|c'|1").unwrap();
+        assert_eq!(record.fields(), [&b"a"[..], b"b|c", b"1"]);
+        let record = Record::from_buf(&mut reader, &mut &b"d|e\n"[..])
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.fields(), [&b"d"[..], b"e"]);
+
+        let schema =
+            infer_schema_with_config(&mut BufReader::new(&b"x|2|3.5\n"[..]), config).unwrap();
+        assert_eq!(
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>(),
+            vec![DataType::Utf8, DataType::Int64, DataType::Float64]
+        );
+
+        let parsers = [FieldParser::Utf8, FieldParser::int64()];
+        let data = [&b"a\t1"[..], b"b\t2"];
+        let batch = Reader::with_config(
+            Config::default().with_delimiter(b'\t'),
+            data.iter().copied(),
+            10,
+            &parsers,
+        )
+        .next_batch()
+        .unwrap()
+        .unwrap();
+        let values = batch.columns()[1]
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(values.values().to_vec(), [1, 2]);
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let mut reader = csv_core::ReaderBuilder::from(Config::default()).build();
+        let record = Record::new(&mut reader, b"\"Smith, John\",\"say \"\"hi\"\"\",3").unwrap();
+        assert_eq!(record.fields(), [&b"Smith, John"[..], b"say \"hi\"", b"3"]);
+
+        let config = Config::default().with_escape(b'\\');
+        assert_eq!(config.escape(), Some(b'\\'));
+        let mut reader = csv_core::ReaderBuilder::from(config).build();
+        let record = Record::new(&mut reader, b"\"a \\\"b\\\" c\",\"d\"\"e\"").unwrap();
+        assert_eq!(record.fields(), [&b"a \"b\" c"[..], b"d\"e"]);
+
+        let mut reader = csv_core::ReaderBuilder::from(Config::default().with_quote(b'\'')).build();
+        let mut input = &b"1,'two\nlines, here'\n2,x\n"[..];
+        let record = Record::from_buf(&mut reader, &mut input).unwrap().unwrap();
+        assert_eq!(record.fields(), [&b"1"[..], b"two\nlines, here"]);
+        let record = Record::from_buf(&mut reader, &mut input).unwrap().unwrap();
+        assert_eq!(record.fields(), [&b"2"[..], b"x"]);
+    }
+
+    #[test]
+    fn binary_field_inference_is_sticky() {
+        let mut inference = FieldInference::default();
+        for field in [&b"1"[..], b"2.5", b"\xff\xfe", b"3", b"abc"] {
+            inference.update(field);
+        }
+        assert_eq!(inference.data_type(), DataType::Binary);
+
+        let mut inference = FieldInference::default();
+        for field in [&b"\xff"[..], b"1", b"2"] {
+            inference.update(field);
+        }
+        assert_eq!(inference.data_type(), DataType::Binary);
+
+        let mut inference = FieldInference::default();
+        for field in [&b"1"[..], b"2.5", b"3"] {
+            inference.update(field);
+        }
+        assert_eq!(inference.data_type(), DataType::Float64);
+    }
+
+    #[test]
+    fn parse_iso8601_durations() {
+        let FieldParser::Int64(parse) = FieldParser::iso8601_duration() else {
+            panic!("duration parser should produce i64");
+        };
+        assert_eq!(parse(b"PT1H30M").unwrap(), 5400);
+        assert_eq!(parse(b"PT45S").unwrap(), 45);
+        assert_eq!(parse(b"P1DT2S").unwrap(), 86_402);
+        assert_eq!(parse(b"P2W").unwrap(), 1_209_600);
+        assert_eq!(parse(b"PT0S").unwrap(), 0);
+        assert_eq!(parse(b"-PT1M").unwrap(), -60);
+        for invalid in [
+            &b"1H30M"[..],
+            b"P",
+            b"PT",
+            b"PT1H30",
+            b"PTH",
+            b"P1M",
+            b"P1Y",
+            b"PT30M1H",
+            b"P1H",
+            b"PT1.5S",
+            b"PT1HT1M",
+            b"P1D1D",
+        ] {
+            assert!(
+                parse(invalid).is_err(),
+                "{}",
+                String::from_utf8_lossy(invalid)
+            );
+        }
+        assert!(parse(format!("PT{}S", i64::MAX).as_bytes()).is_ok());
+        assert!(parse(format!("PT{}H", i64::MAX).as_bytes()).is_err());
+
+        let parsers = [FieldParser::iso8601_duration()];
+        let data = [&b"PT1H30M"[..], b"PT1X"];
+        let mut reader = Reader::new(data.iter().copied(), 10, &parsers)
+            .with_on_parse_error(OnParseError::Error);
+        assert!(reader.next_batch().is_err());
+    }
+
+    #[test]
+    fn parse_radix_aware_integers() {
+        let FieldParser::Int64(parse) = FieldParser::int64_radix_aware() else {
+            panic!("int64 parser should produce i64");
+        };
+        assert_eq!(parse(b"0xFF").unwrap(), 255);
+        assert_eq!(parse(b"0X1f").unwrap(), 31);
+        assert_eq!(parse(b"0o17").unwrap(), 15);
+        assert_eq!(parse(b"0b101").unwrap(), 5);
+        assert_eq!(parse(b"-0x10").unwrap(), -16);
+        assert_eq!(parse(b"+0o7").unwrap(), 7);
+        assert_eq!(parse(b"017").unwrap(), 17);
+        assert_eq!(parse(b"-42").unwrap(), -42);
+        assert_eq!(parse(b"-0x8000000000000000").unwrap(), i64::MIN);
+        assert!(parse(b"0x8000000000000000").is_err());
+        assert!(parse(b"0x").is_err());
+        assert!(parse(b"0x-1").is_err());
+        assert!(parse(b"0o8").is_err());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn parse_quoted_numbers() {
+        let FieldParser::Int64(parse_int) = FieldParser::int64() else {
+            panic!("int64 parser should produce i64");
+        };
+        assert_eq!(parse_int(b"\"42\"").unwrap(), 42);
+        assert_eq!(parse_int(b"42").unwrap(), 42);
+        assert!(parse_int(b"\"42").is_err());
+        assert!(parse_int(b"\"\"42\"\"").is_err());
+        let FieldParser::Float64(parse_float) = FieldParser::float64() else {
+            panic!("float64 parser should produce f64");
+        };
+        assert_eq!(parse_float(b"\"1.5\"").unwrap(), 1.5);
+
+        let mut reader = csv_core::ReaderBuilder::new().quoting(false).build();
+        let record = Record::new(&mut reader, b"\"42\",7\n").unwrap();
+        assert_eq!(record.get(0), Some(&b"\"42\""[..]));
+        assert_eq!(parse_int(record.get(0).unwrap()).unwrap(), 42);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn parse_currency() {
+        let FieldParser::Float64(parse) = FieldParser::currency() else {
+            panic!("currency parser should produce f64");
+        };
+        assert_eq!(parse(b"$1,234.56").unwrap(), 1234.56);
+        assert_eq!(parse(b"(500.00)").unwrap(), -500.0);
+        assert_eq!(parse(b"-$5").unwrap(), -5.0);
+        assert_eq!(parse(b"1234").unwrap(), 1234.0);
+        assert!(parse(b"$1,23.4").is_err());
+        assert!(parse(b"-(500.00)").is_err());
+        assert!(parse(b"(500.00").is_err());
+        assert!(parse(b"$abc").is_err());
+    }
+
+    #[test]
+    fn parse_timestamp_variants() {
+        let FieldParser::Int64(parse) = FieldParser::timestamp() else {
+            panic!("timestamp parser should produce i64");
+        };
+        let expected = NaiveDate::from_ymd_opt(2020, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 30, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_nanos_opt()
+            .unwrap();
+        for input in [
+            &b"2020-01-01T12:30:00Z"[..],
+            b"2020-01-01T12:30:00.000Z",
+            b"2020-01-01T12:30:00+00:00",
+            b"2020-01-01 12:30:00",
+            b"2020-01-01 12:30:00Z",
+            b"2020-01-01T12:30:00",
+        ] {
+            assert_eq!(parse(input).unwrap(), expected);
+        }
+        for input in [
+            &b"2020-01-01T21:30:00+09:00"[..],
+            b"2020-01-01 07:00:00.000-05:30",
+            b"2019-12-31T23:30:00-13:00",
+        ] {
+            assert_eq!(parse(input).unwrap(), expected);
+        }
+        assert!(parse(b"2020-01-01").is_err());
+        assert!(parse(b"12:30:00").is_err());
+        assert!(parse(b"2020-01-01T12:30:00+25:00").is_err());
+    }
+
+    #[test]
+    fn parse_lenient_timestamp() {
+        let FieldParser::Int64(parse) = FieldParser::lenient_timestamp() else {
+            panic!("timestamp parser should produce i64");
+        };
+        let expected = parse(b"2020-01-01T00:00:00Z").unwrap();
+        assert_eq!(parse(b"1577836800").unwrap(), expected);
+        assert_eq!(parse(b"2020-01-01 00:00:00").unwrap(), expected);
+        assert!(parse(b"9223372036854775807").is_err());
+        assert!(parse(b"2020-01-01").is_err());
+    }
+
+    /// Generates CSV-like input with a simple xorshift generator, favoring the
+    /// bytes that affect record boundaries.
+    fn random_csv(seed: &mut u64, len: usize) -> Vec<u8> {
+        const ALPHABET: &[u8] = b"ab1,,\"\"\n\n\r \xff";
+        (0..len)
+            .map(|_| {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 7;
+                *seed ^= *seed << 17;
+                ALPHABET[usize::try_from(*seed % ALPHABET.len() as u64).unwrap()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn from_buf_read_error() {
+        /// Fails after returning `data`.
+        struct FailingReader<'a> {
+            data: &'a [u8],
+        }
+
+        impl Read for FailingReader<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.data.is_empty() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::ConnectionReset,
+                        "connection reset",
+                    ));
+                }
+                self.data.read(buf)
+            }
+        }
+
+        let mut input = BufReader::with_capacity(4, FailingReader { data: b"a,b\nc," });
+        let mut reader = csv_core::Reader::new();
+        let record = Record::from_buf(&mut reader, &mut input).unwrap().unwrap();
+        assert_eq!(record.fields(), [&b"a"[..], b"b"]);
+        let error = Record::from_buf(&mut reader, &mut input)
+            .err()
+            .expect("an I/O error");
+        assert_eq!(error.kind(), std::io::ErrorKind::ConnectionReset);
+
+        let mut input = BufReader::new(FailingReader { data: b"" });
+        assert!(infer_schema(&mut input).is_err());
+    }
+
+    #[test]
+    fn from_buf_matches_reference_parser() {
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for len in (0..64).chain([255, 1024, 4099]) {
+            let input = random_csv(&mut seed, len);
+            let expected: Vec<Vec<Vec<u8>>> = ::csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(input.as_slice())
+                .byte_records()
+                .map(|r| r.unwrap().iter().map(<[u8]>::to_vec).collect())
+                .collect();
+            for capacity in [1, 2, 3, 7, 16, 64, 8192] {
+                let mut buf = BufReader::with_capacity(capacity, input.as_slice());
+                let mut reader = csv_core::Reader::new();
+                let mut records: Vec<Vec<Vec<u8>>> = Vec::new();
+                while let Some(record) = Record::from_buf(&mut reader, &mut buf).unwrap() {
+                    records.push(record.fields().into_iter().map(<[u8]>::to_vec).collect());
+                }
+                assert_eq!(
+                    records,
+                    expected,
+                    "input {:?} with capacity {capacity}",
+                    String::from_utf8_lossy(&input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn multi_byte_delimiter() {
+        let record = Record::split(b"a||b||c\r\n", b"||").unwrap();
+        assert_eq!(record.fields(), [&b"a"[..], b"b", b"c"]);
+        let record = Record::split(b"||x|", b"||").unwrap();
+        assert_eq!(record.fields(), [&b""[..], b"x|"]);
+        assert!(Record::split(b"\n", b"||").is_none());
+
+        let parsers = [FieldParser::int64(), FieldParser::Utf8];
+        let data = [&b"1::\"a\""[..], b"2::b,c"];
+        let batch = Reader::new(data.iter().copied(), 10, &parsers)
+            .with_multi_byte_delimiter(b"::")
+            .next_batch()
+            .unwrap()
+            .unwrap();
+        let values = batch.columns()[1]
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(values, [Some("\"a\""), Some("b,c")]);
+    }
+
+    #[test]
+    fn reassemble() {
+        let mut reader = csv_core::Reader::new();
+        let record = Record::new(&mut reader, b"a,\"b,c\",\"say \"\"hi\"\"\",,d\n").unwrap();
+        assert_eq!(
+            record.reassemble(b','),
+            b"a,\"b,c\",\"say \"\"hi\"\"\",,d".to_vec()
+        );
+        assert_eq!(
+            record.reassemble(b'\t'),
+            b"a\tb,c\t\"say \"\"hi\"\"\"\t\td".to_vec()
+        );
+
+        let record = Record::new(&mut reader, b"\"x\",y\n").unwrap();
+        assert_eq!(record.reassemble(b','), b"x,y".to_vec());
+    }
+
+    #[test]
+    fn embedded_nul() {
+        let input = b"a\0b,\0,c\n";
+        let mut reader = csv_core::Reader::new();
+        let record = Record::new(&mut reader, input).unwrap();
+        assert_eq!(record.get(0), Some(&b"a\0b"[..]));
+        assert_eq!(record.get(1), Some(&b"\0"[..]));
+        assert_eq!(record.get(2), Some(&b"c"[..]));
+
+        let parsers = [FieldParser::Utf8, FieldParser::Binary, FieldParser::Utf8];
+        let data = [&input[..]];
+        let mut reader = Reader::new(data.iter().copied(), 1, &parsers);
+        let batch = reader.next_batch().unwrap().unwrap();
+        let utf8 = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(utf8.value(0), "a\0b");
+        let binary = batch.columns()[1]
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .unwrap();
+        assert_eq!(binary.value(0), b"\0");
+    }
+
     #[test]
     fn parse_records() {
         let parsers = [
@@ -654,11 +2582,314 @@ mod tests {
         assert_eq!(result, columns);
     }
 
+    #[test]
+    fn expected_rows() {
+        let parsers = [FieldParser::int64(), FieldParser::Utf8];
+        let data: Vec<Vec<u8>> = (0..10).map(|i| format!("{i},s{i}").into_bytes()).collect();
+        let read = |expected_rows: Option<usize>| {
+            let mut reader = Reader::new(data.iter().map(std::vec::Vec::as_slice), 4, &parsers);
+            if let Some(expected_rows) = expected_rows {
+                reader = reader.with_expected_rows(expected_rows);
+            }
+            let mut capacities = Vec::new();
+            let mut num_rows = Vec::new();
+            loop {
+                capacities.push(reader.batch_capacity());
+                let Some(batch) = reader.next_batch().unwrap() else {
+                    break;
+                };
+                num_rows.push(batch.columns()[0].len());
+            }
+            (capacities, num_rows)
+        };
+        assert_eq!(read(None), (vec![4, 4, 4, 4], vec![4, 4, 2]));
+        assert_eq!(read(Some(10)), (vec![4, 4, 2, 4], vec![4, 4, 2]));
+        assert_eq!(read(Some(5)), (vec![4, 1, 4, 4], vec![4, 4, 2]));
+        assert_eq!(read(Some(0)), (vec![4, 4, 4, 4], vec![4, 4, 2]));
+        assert_eq!(read(Some(100)), (vec![4, 4, 4, 4], vec![4, 4, 2]));
+    }
+
+    #[test]
+    fn strict_field_count() {
+        let parsers = [FieldParser::int64(), FieldParser::Utf8];
+        let data = [&b"1,a"[..], b"2,b,extra"];
+        let read = |tolerance: Option<usize>| {
+            let mut reader = Reader::new(data.iter().copied(), 10, &parsers);
+            if let Some(tolerance) = tolerance {
+                reader = reader.with_strict_field_count(tolerance);
+            }
+            reader
+                .next_batch()
+                .map(|batch| batch.map(|b| b.columns()[0].len()))
+        };
+        assert_eq!(read(None).unwrap(), Some(2));
+        assert!(read(Some(0)).is_err());
+        assert_eq!(read(Some(1)).unwrap(), Some(2));
+    }
+
+    #[test]
+    fn default_value() {
+        let parsers = [
+            FieldParser::int64(),
+            FieldParser::int64(),
+            FieldParser::Utf8,
+        ];
+        let data = [&b"1,1,a"[..], b"x,x,b", b",,c"];
+        let read = |column: usize, value: DefaultValue, threshold: usize| {
+            Reader::new(data.iter().copied(), 10, &parsers)
+                .with_default_value(column, value)
+                .with_parallelism_threshold(threshold)
+                .next_batch()
+                .map(|batch| {
+                    let batch = batch.unwrap();
+                    [0, 1].map(|i| {
+                        batch.columns()[i]
+                            .as_any()
+                            .downcast_ref::<Int64Array>()
+                            .unwrap()
+                            .values()
+                            .to_vec()
+                    })
+                })
+        };
+        let threshold = DEFAULT_PARALLELISM_THRESHOLD;
+        assert_eq!(
+            read(0, DefaultValue::Int64(-1), threshold).unwrap(),
+            [vec![1, -1, -1], vec![1, 0, 0]]
+        );
+        assert_eq!(
+            read(1, DefaultValue::Int64(-1), 0).unwrap(),
+            [vec![1, 0, 0], vec![1, -1, -1]]
+        );
+        assert!(read(0, DefaultValue::Float64(-1.0), threshold).is_err());
+        assert!(read(2, DefaultValue::Int64(-1), threshold).is_err());
+    }
+
+    #[test]
+    fn invalid_utf8_location() {
+        let parsers = [FieldParser::Utf8, FieldParser::Utf8];
+        let data = [&b"a,b"[..], b"c,d", b"e,\xff"];
+        let Err(ArrowError::ParseError(message)) =
+            Reader::new(data.iter().copied(), 10, &parsers).next_batch()
+        else {
+            panic!("invalid UTF-8 should be rejected");
+        };
+        assert!(message.starts_with("field 1 of record 2 of the batch: "));
+    }
+
+    #[test]
+    fn on_parse_error() {
+        let parsers = [FieldParser::int64(), FieldParser::float64()];
+        let data = [&b"1,1.5"[..], b"x,y", b","];
+        let read = |policy: OnParseError| {
+            Reader::new(data.iter().copied(), 10, &parsers)
+                .with_on_parse_error(policy)
+                .next_batch()
+                .map(|batch| {
+                    let batch = batch.unwrap();
+                    let ints = batch.columns()[0]
+                        .as_any()
+                        .downcast_ref::<Int64Array>()
+                        .unwrap()
+                        .iter()
+                        .collect::<Vec<_>>();
+                    let floats = batch.columns()[1]
+                        .as_any()
+                        .downcast_ref::<Float64Array>()
+                        .unwrap()
+                        .iter()
+                        .collect::<Vec<_>>();
+                    (ints, floats)
+                })
+        };
+        assert_eq!(
+            read(OnParseError::Default).unwrap(),
+            (
+                vec![Some(1), Some(0), Some(0)],
+                vec![Some(1.5), Some(0.0), Some(0.0)]
+            )
+        );
+        assert_eq!(
+            read(OnParseError::Null).unwrap(),
+            (
+                vec![Some(1), None, Some(0)],
+                vec![Some(1.5), None, Some(0.0)]
+            )
+        );
+        assert!(read(OnParseError::Error).is_err());
+    }
+
+    #[test]
+    fn float_promotion() {
+        let parsers = [FieldParser::int64(), FieldParser::int64()];
+        let data = [&b"1,1"[..], b"2,2", b"3.5,3", b"4,x", b"5,5", b"6.5,6"];
+        let mut reader = Reader::new(data.iter().copied(), 4, &parsers)
+            .with_float_promotion()
+            .with_default_value(0, DefaultValue::Int64(-1));
+
+        let batch = reader.next_batch().unwrap().unwrap();
+        let floats = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(floats.values(), &[1.0, 2.0, 3.5, 4.0]);
+        let ints = batch.columns()[1]
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(ints.values(), &[1, 2, 3, 0]);
+        assert_eq!(reader.promoted_columns(), [0]);
+
+        let batch = reader.next_batch().unwrap().unwrap();
+        let floats = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(floats.values(), &[5.0, 6.5]);
+        assert_eq!(reader.promoted_columns(), [0]);
+
+        let data = [&b"1,1"[..], b",2", b"2.5,3"];
+        let mut reader = Reader::new(data.iter().copied(), 10, &parsers)
+            .with_float_promotion()
+            .with_default_value(0, DefaultValue::Int64(-1));
+        let batch = reader.next_batch().unwrap().unwrap();
+        let floats = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(floats.values(), &[1.0, -1.0, 2.5]);
+
+        let data = [&b"1,1"[..], b",2", b"x,3"];
+        let mut reader = Reader::new(data.iter().copied(), 10, &parsers).with_float_promotion();
+        let batch = reader.next_batch().unwrap().unwrap();
+        assert_eq!(batch.columns()[0].data_type(), &DataType::Int64);
+        assert!(reader.promoted_columns().is_empty());
+
+        // Non-finite values do not promote a column.
+        let data = [&b"1,1"[..], b"inf,2", b"NaN,3"];
+        let mut reader = Reader::new(data.iter().copied(), 10, &parsers).with_float_promotion();
+        let batch = reader.next_batch().unwrap().unwrap();
+        assert_eq!(batch.columns()[0].data_type(), &DataType::Int64);
+        assert!(reader.promoted_columns().is_empty());
+
+        // A custom parser still parses the integers of a promoted column.
+        let parsers = [FieldParser::int64_radix_aware()];
+        let data = [&b"0x10"[..], b"1.5", b"0b11", b"inf"];
+        let mut reader = Reader::new(data.iter().copied(), 2, &parsers).with_float_promotion();
+        let batch = reader.next_batch().unwrap().unwrap();
+        let floats = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(floats.values(), &[16.0, 1.5]);
+        let batch = reader.next_batch().unwrap().unwrap();
+        let floats = batch.columns()[0]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(floats.values(), &[3.0, 0.0]);
+    }
+
+    #[test]
+    fn next_batch_with_failures() {
+        let parsers = [
+            FieldParser::int64(),
+            FieldParser::Utf8,
+            FieldParser::float64(),
+        ];
+        let data = [&b"1,a,1.5"[..], b"x,b,2.5", b"3,c,y", b"z,d,"];
+        for threshold in [usize::MAX, 0] {
+            let (batch, failures) = Reader::new(data.iter().copied(), 10, &parsers)
+                .with_parallelism_threshold(threshold)
+                .next_batch_with_failures()
+                .unwrap()
+                .unwrap();
+            assert_eq!(batch.columns()[0].len(), 4);
+            let failures = failures
+                .iter()
+                .map(|f| (f.row, f.column))
+                .collect::<Vec<_>>();
+            assert_eq!(failures, [(1, 0), (3, 0), (2, 2)]);
+        }
+    }
+
+    #[test]
+    fn parallelism_threshold() {
+        let parsers = [
+            FieldParser::int64(),
+            FieldParser::Utf8,
+            FieldParser::float64(),
+            FieldParser::Binary,
+        ];
+        let data: Vec<Vec<u8>> = (0..100)
+            .map(|i| format!("{i},s{i},{i}.5,b{i}").into_bytes())
+            .collect();
+        let read = |threshold: usize| {
+            let reader = Reader::new(
+                data.iter().map(std::vec::Vec::as_slice),
+                data.len(),
+                &parsers,
+            )
+            .with_parallelism_threshold(threshold);
+            (reader.parses_in_parallel(3), reader.parses_in_parallel(100))
+        };
+        assert_eq!(read(DEFAULT_PARALLELISM_THRESHOLD), (false, false));
+        assert_eq!(read(100), (false, true));
+        assert_eq!(read(0), (true, true));
+
+        let batches: Vec<Vec<Column>> = [usize::MAX, 0]
+            .into_iter()
+            .map(|threshold| {
+                let mut reader = Reader::new(
+                    data.iter().map(std::vec::Vec::as_slice),
+                    data.len(),
+                    &parsers,
+                )
+                .with_parallelism_threshold(threshold);
+                let batch = reader.next_batch().unwrap().unwrap();
+                batch.columns().iter().map(|c| c.clone().into()).collect()
+            })
+            .collect();
+        assert_eq!(batches[0].len(), 4);
+        assert_eq!(batches[0], batches[1]);
+    }
+
+    #[test]
+    fn parsers_by_name() {
+        let variants: Vec<_> = PARSER_NAMES
+            .iter()
+            .map(|name| format!("{:?}", parser_by_name(name).unwrap()))
+            .collect();
+        assert_eq!(
+            variants,
+            vec![
+                "Binary", "Boolean", "Float64", "Float32", "Float64", "Float32", "Float64",
+                "Int16", "Int32", "Int64", "Int64", "Int8", "UInt32", "Int64", "Int64", "Int64",
+                "UInt32", "UInt64", "Utf8"
+            ]
+        );
+        assert!(parser_by_name("Int64").is_none());
+        assert!(parser_by_name("").is_none());
+
+        let Some(FieldParser::UInt32(parse)) = parser_by_name("ipaddr") else {
+            panic!("ipaddr parser should produce u32");
+        };
+        assert_eq!(parse(b"127.0.0.1").unwrap(), u32::from(Ipv4Addr::LOCALHOST));
+        assert!(parse(b"127.0.0").is_err());
+
+        let Some(FieldParser::Int64(parse)) = parser_by_name("iso8601_duration") else {
+            panic!("iso8601_duration parser should produce i64");
+        };
+        assert_eq!(parse(b"PT1H30M").unwrap(), 5400);
+    }
+
     #[test]
     fn config() {
         let config = Config {
             delimiter: b' ',
             quote: b'\t',
+            terminator: None,
+            escape: None,
         };
         assert_tokens(
             &config,
@@ -684,7 +2915,42 @@ mod tests {
         let config = Config {
             delimiter: b' ', // b' ' = 32
             quote: b'*',     // b'*' = 42
+            terminator: None,
+            escape: None,
         };
         assert_eq!(config, serde_json::from_str(config_str).unwrap());
+
+        let config = Config::default().with_terminator(0x1e);
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"delimiter":44,"quote":34,"terminator":30}"#
+        );
+
+        let config = Config::default().with_escape(b'\\');
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"delimiter":44,"quote":34,"escape":92}"#
+        );
+    }
+
+    #[test]
+    fn custom_terminator() {
+        let config = Config::default().with_terminator(0x1e);
+        let mut csv_reader = csv_core::ReaderBuilder::from(config).build();
+        let mut input: &[u8] = b"a,1\x1eb,\"2\n3\"\x1ec,4";
+        let mut fields = Vec::new();
+        while let Some(record) = Record::from_buf(&mut csv_reader, &mut input).unwrap() {
+            fields.push(record.fields().concat());
+        }
+        assert_eq!(
+            fields,
+            vec![b"a1".to_vec(), b"b2\n3".to_vec(), b"c4".to_vec()]
+        );
+
+        let parsers = [FieldParser::Utf8, FieldParser::int64()];
+        let data = [&b"x,5\x1e"[..]];
+        let mut reader = Reader::with_config(config, data.iter().copied(), 1, &parsers);
+        let batch = reader.next_batch().unwrap().unwrap();
+        assert_eq!(batch.columns()[0].len(), 1);
     }
 }
@@ -5,7 +5,10 @@ mod table;
 
 pub use arrow;
 pub use stats::{
-    ColumnStatistics, Description, Element, ElementCount, FloatRange, GroupCount, GroupElement,
-    GroupElementCount, NLargestCount,
+    BinaryEncoding, ColumnStatistics, Description, Element, ElementCount, FloatRange, GroupCount,
+    GroupElement, GroupElementCount, Interval, NLargestCount,
+};
+pub use table::{
+    Column, ColumnType, CompareOp, FlagColumn, LazyStatistics, Predicate, RowFilter, SchemaDrift,
+    SchemaExt, Table, TableError, TypedColumn,
 };
-pub use table::{Column, ColumnType, Table};
@@ -1,10 +1,11 @@
 use arrow::datatypes::{Float64Type, Int64Type, UInt32Type, UInt64Type};
+use base64::prelude::{Engine, BASE64_STANDARD};
 use chrono::{DateTime, NaiveDateTime};
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use statistical::{mean, population_standard_deviation};
-use std::collections::HashMap;
-use std::fmt;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write as _};
 use std::hash::Hash;
 use std::iter::Iterator;
 use std::net::{IpAddr, Ipv4Addr};
@@ -14,6 +15,49 @@ use crate::table::{Column, ColumnType};
 const MAX_TIME_INTERVAL: u32 = 86_400; // one day in seconds
 const MIN_TIME_INTERVAL: u32 = 30; // seconds
 
+/// The label of an enum id missing in its reverse map, by default.
+pub(crate) const UNKNOWN_ENUM_LABEL: &str = "_NO_MAP_";
+
+/// A length of time by which timestamps are grouped.
+///
+/// An interval shorter than 30 seconds, including zero, is treated as 30
+/// seconds, both when grouping and when finding missing intervals. Longer
+/// intervals, including those of multiple days, are kept as they are, and
+/// the groups are counted from the Unix epoch.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Interval {
+    Seconds(u32),
+    Minutes(u32),
+    Hours(u32),
+    Days(u32),
+}
+
+impl Interval {
+    /// Returns the length of the interval in seconds, saturating at
+    /// `u32::MAX`.
+    #[must_use]
+    pub fn as_secs(self) -> u32 {
+        match self {
+            Self::Seconds(n) => n,
+            Self::Minutes(n) => n.saturating_mul(60),
+            Self::Hours(n) => n.saturating_mul(3_600),
+            Self::Days(n) => n.saturating_mul(86_400),
+        }
+    }
+
+    /// Returns the length of the interval in seconds, raised to the minimum
+    /// of 30 seconds.
+    pub(crate) fn grouping_secs(self) -> u32 {
+        self.as_secs().max(MIN_TIME_INTERVAL)
+    }
+
+    /// Returns an interval of `secs` seconds, shortened to a day if longer,
+    /// as the functions taking an interval in seconds group timestamps.
+    pub(crate) fn clamped_secs(secs: u32) -> Self {
+        Self::Seconds(secs.min(MAX_TIME_INTERVAL))
+    }
+}
+
 /// The underlying data type of a column description.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Element {
@@ -28,6 +72,52 @@ pub enum Element {
     DateTime(NaiveDateTime),
 }
 
+/// A textual encoding of binary values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BinaryEncoding {
+    /// Hexadecimal digits in lowercase, e.g., `0aff`.
+    #[default]
+    LowerHex,
+    /// Hexadecimal digits in uppercase, e.g., `0AFF`.
+    UpperHex,
+    /// Base64 with padding, as in RFC 4648, e.g., `Cv8=`.
+    Base64,
+}
+
+impl BinaryEncoding {
+    /// Encodes `bytes` into a string.
+    #[must_use]
+    pub fn encode(self, bytes: &[u8]) -> String {
+        let mut encoded = String::with_capacity(bytes.len() * 2);
+        match self {
+            Self::LowerHex => {
+                for b in bytes {
+                    let _ = write!(encoded, "{b:02x}");
+                }
+            }
+            Self::UpperHex => {
+                for b in bytes {
+                    let _ = write!(encoded, "{b:02X}");
+                }
+            }
+            Self::Base64 => BASE64_STANDARD.encode_string(bytes, &mut encoded),
+        }
+        encoded
+    }
+}
+
+impl Element {
+    /// Returns the value as a string, as `to_string` does, except that a
+    /// `Binary` value is encoded with `encoding` instead of lowercase hex.
+    #[must_use]
+    pub fn to_string_with(&self, encoding: BinaryEncoding) -> String {
+        match self {
+            Self::Binary(x) => encoding.encode(x),
+            _ => self.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Hash)]
 pub enum GroupElement {
     Int(i64),
@@ -50,7 +140,7 @@ impl fmt::Display for Element {
             Self::Int(x) => write!(f, "{x}"),
             Self::UInt(x) => write!(f, "{x}"),
             Self::Enum(x) | Self::Text(x) => write!(f, "{x}"),
-            Self::Binary(x) => write!(f, "{x:#?}"),
+            Self::Binary(x) => write!(f, "{}", BinaryEncoding::LowerHex.encode(x)),
             Self::Float(x) => write!(f, "{x}"),
             Self::FloatRange(x) => {
                 if x.smallest == 0.0_f64 && x.largest == 0.0_f64 {
@@ -65,6 +155,41 @@ impl fmt::Display for Element {
     }
 }
 
+impl PartialOrd for Element {
+    fn partial_cmp(&self, other: &Element) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Self::Int(s), Self::Int(o)) => Some(s.cmp(o)),
+            (Self::UInt(s), Self::UInt(o)) => Some(s.cmp(o)),
+            (Self::Enum(s), Self::Enum(o)) | (Self::Text(s), Self::Text(o)) => Some(s.cmp(o)),
+            (Self::Float(s), Self::Float(o)) => s.partial_cmp(o),
+            (Self::FloatRange(s), Self::FloatRange(o)) => s.smallest.partial_cmp(&o.smallest),
+            (Self::Binary(s), Self::Binary(o)) => Some(s.cmp(o)),
+            (Self::IpAddr(s), Self::IpAddr(o)) => Some(s.cmp(o)),
+            (Self::DateTime(s), Self::DateTime(o)) => Some(s.cmp(o)),
+            _ => None,
+        }
+    }
+}
+
+impl GroupElement {
+    /// Returns the value as a string: a datetime in ISO 8601, an IP address
+    /// in the usual notation, and an integer in decimal. A `UInt` value is
+    /// taken as an enum id and labeled with `reverse_map` if given.
+    #[must_use]
+    pub fn to_display_string(&self, reverse_map: Option<&HashMap<u64, Vec<String>>>) -> String {
+        match self {
+            Self::Int(v) => v.to_string(),
+            Self::UInt(v) => reverse_map.map_or_else(
+                || v.to_string(),
+                |map| enum_label(map, u64::from(*v), UNKNOWN_ENUM_LABEL),
+            ),
+            Self::Enum(v) | Self::Text(v) => v.clone(),
+            Self::IpAddr(v) => v.to_string(),
+            Self::DateTime(v) => v.format("%Y-%m-%dT%H:%M:%S%.f").to_string(),
+        }
+    }
+}
+
 impl PartialOrd for GroupElement {
     fn partial_cmp(&self, other: &GroupElement) -> Option<std::cmp::Ordering> {
         match (self, other) {
@@ -105,6 +230,8 @@ pub struct NLargestCount {
     number_of_elements: usize,
     top_n: Vec<ElementCount>,
     mode: Option<Element>,
+    #[serde(default)]
+    remaining_count: usize,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -154,6 +281,117 @@ impl fmt::Display for NLargestCount {
     }
 }
 
+impl ColumnStatistics {
+    /// Merges the statistics of another partition of the same column into
+    /// `self`, keeping the `number_of_top_n` most frequent values.
+    ///
+    /// See [`Description::merge`] and [`NLargestCount::merge`] for how each
+    /// part is combined.
+    pub fn merge(&mut self, other: &Self, number_of_top_n: u32) {
+        self.description.merge(&other.description);
+        self.n_largest_count
+            .merge(&other.n_largest_count, number_of_top_n);
+    }
+
+    /// Renders the statistics of `columns`, each paired with its name, as
+    /// gauges in the Prometheus text exposition format. Each metric family
+    /// has one `# TYPE` line, followed by a sample for each column labeled
+    /// with `table` and the name of the column.
+    ///
+    /// The count and the number of distinct values are always rendered. The
+    /// minimum, maximum, and mean are rendered only for numeric values, and
+    /// a family without any sample is omitted. Non-finite values are
+    /// rendered as `+Inf`, `-Inf`, or `NaN`.
+    #[must_use]
+    pub fn to_prometheus<'a, C>(table: &str, columns: C) -> String
+    where
+        C: IntoIterator<Item = (&'a str, &'a ColumnStatistics)>,
+    {
+        let columns: Vec<_> = columns
+            .into_iter()
+            .map(|(column, statistics)| {
+                let labels = format!(
+                    "table=\"{}\",column=\"{}\"",
+                    escape_label_value(table),
+                    escape_label_value(column)
+                );
+                (labels, statistics.gauges())
+            })
+            .collect();
+
+        let mut out = String::new();
+        for (i, name) in GAUGE_NAMES.iter().enumerate() {
+            let mut samples = columns
+                .iter()
+                .filter_map(|(labels, gauges)| Some((labels, gauges[i]?)))
+                .peekable();
+            if samples.peek().is_none() {
+                continue;
+            }
+            let _ = writeln!(out, "# TYPE structured_column_{name} gauge");
+            for (labels, value) in samples {
+                let _ = writeln!(
+                    out,
+                    "structured_column_{name}{{{labels}}} {}",
+                    sample_value(value)
+                );
+            }
+        }
+        out
+    }
+
+    /// Returns the values of the gauges named in `GAUGE_NAMES`, if any.
+    fn gauges(&self) -> [Option<f64>; GAUGE_NAMES.len()] {
+        #[allow(clippy::cast_precision_loss)] // 52-bit precision is good enough
+        fn numeric(elem: Option<&Element>) -> Option<f64> {
+            match elem? {
+                Element::Int(v) => Some(*v as f64),
+                Element::UInt(v) => Some(*v as f64),
+                Element::Float(v) => Some(*v),
+                _ => None,
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        [
+            Some(self.description.count() as f64),
+            Some(self.n_largest_count.number_of_elements() as f64),
+            numeric(self.description.min()),
+            numeric(self.description.max()),
+            self.description.mean(),
+        ]
+    }
+}
+
+/// The names of the gauges rendered by [`ColumnStatistics::to_prometheus`],
+/// in order.
+const GAUGE_NAMES: [&str; 5] = ["count", "distinct", "min", "max", "mean"];
+
+/// Formats a sample value for the Prometheus text exposition format, which
+/// spells non-finite values differently from Rust.
+fn sample_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value.is_sign_positive() {
+            "+Inf"
+        } else {
+            "-Inf"
+        }
+        .to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escapes a label value for the Prometheus text exposition format.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 impl Description {
     #[must_use]
     pub fn new(
@@ -196,23 +434,77 @@ impl Description {
     pub fn max(&self) -> Option<&Element> {
         self.max.as_ref()
     }
+
+    /// Merges the description of another partition of the same column into
+    /// `self`, as if it had been computed over the rows of both.
+    ///
+    /// The mean and the population standard deviation are combined using the
+    /// counts of both partitions, so the result matches the one over the
+    /// union up to floating-point rounding.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+
+        #[allow(clippy::cast_precision_loss)] // 52-bit precision is good enough
+        let (n1, n2) = (self.count as f64, other.count as f64);
+        if let (Some(m1), Some(m2), Some(s1), Some(s2)) =
+            (self.mean, other.mean, self.s_deviation, other.s_deviation)
+        {
+            let n = n1 + n2;
+            let delta = m2 - m1;
+            let m2_sum = s1 * s1 * n1 + s2 * s2 * n2 + delta * delta * n1 * n2 / n;
+            self.mean = Some(m1 + delta * n2 / n);
+            self.s_deviation = Some((m2_sum / n).sqrt());
+        } else if self.mean.is_none() {
+            self.mean = other.mean;
+            self.s_deviation = other.s_deviation;
+        }
+        self.count += other.count;
+
+        match (&self.min, &other.min) {
+            (Some(s), Some(o)) if o < s => self.min = Some(o.clone()),
+            (None, Some(o)) => self.min = Some(o.clone()),
+            _ => {}
+        }
+        match (&self.max, &other.max) {
+            (Some(s), Some(o)) if o > s => self.max = Some(o.clone()),
+            (None, Some(o)) => self.max = Some(o.clone()),
+            _ => {}
+        }
+    }
 }
 
 impl NLargestCount {
+    /// Creates an `NLargestCount` whose `top_n` includes all the values, i.e.,
+    /// with no remaining count.
     #[must_use]
     pub fn new(number_of_elements: usize, top_n: Vec<ElementCount>, mode: Option<Element>) -> Self {
         Self {
             number_of_elements,
             top_n,
             mode,
+            remaining_count: 0,
         }
     }
 
+    /// Returns the number of distinct values, including those not in
+    /// `top_n`.
     #[must_use]
     pub fn number_of_elements(&self) -> usize {
         self.number_of_elements
     }
 
+    /// Returns the total count of the values not in `top_n`.
+    #[must_use]
+    pub fn remaining_count(&self) -> usize {
+        self.remaining_count
+    }
+
     #[must_use]
     pub fn top_n(&self) -> &Vec<ElementCount> {
         &self.top_n
@@ -222,6 +514,92 @@ impl NLargestCount {
     pub fn mode(&self) -> Option<&Element> {
         self.mode.as_ref()
     }
+
+    /// Merges the most frequent values of another partition of the same
+    /// column into `self`, summing the counts of equal values, and keeps the
+    /// `number_of_top_n` most frequent values in `top_n`.
+    ///
+    /// The number of elements is exact if neither `self` nor `other`
+    /// truncated its top N, i.e., both hold every distinct value. Otherwise,
+    /// values outside either top N are unknown, and the number of elements is
+    /// a lower bound.
+    pub fn merge(&mut self, other: &Self, number_of_top_n: u32) {
+        let complete = self.top_n.len() == self.number_of_elements
+            && other.top_n.len() == other.number_of_elements;
+        let len = usize::try_from(number_of_top_n).unwrap_or(usize::MAX);
+        let total = self.total_count() + other.total_count();
+
+        for elem in &other.top_n {
+            if let Some(e) = self.top_n.iter_mut().find(|e| e.value == elem.value) {
+                e.count += elem.count;
+            } else {
+                self.top_n.push(elem.clone());
+            }
+        }
+        self.top_n.sort_by_key(|e| std::cmp::Reverse(e.count));
+
+        self.number_of_elements = if complete {
+            self.top_n.len()
+        } else {
+            self.number_of_elements
+                .max(other.number_of_elements)
+                .max(self.top_n.len())
+        };
+        self.top_n.truncate(len);
+        self.remaining_count = total - self.top_n.iter().map(|e| e.count).sum::<usize>();
+        self.mode = self.top_n.first().map(|e| e.value.clone());
+    }
+
+    /// Returns the total count of all the values.
+    fn total_count(&self) -> usize {
+        self.remaining_count + self.top_n.iter().map(|e| e.count).sum::<usize>()
+    }
+}
+
+impl GroupCount {
+    /// Returns the values and counts of the series as two parallel vectors,
+    /// in the order of the series.
+    #[must_use]
+    pub fn split_series(&self) -> (Vec<GroupElement>, Vec<usize>) {
+        self.series
+            .iter()
+            .map(|elem| (elem.value.clone(), elem.count))
+            .unzip()
+    }
+
+    /// Returns the datetimes, at every `interval` from the earliest datetime
+    /// in the series up to the latest one, that are missing in the series.
+    ///
+    /// Values other than datetimes are ignored. An interval shorter than 30
+    /// seconds is treated as 30 seconds, as in grouping.
+    #[must_use]
+    pub fn missing_intervals(&self, interval: Interval) -> Vec<NaiveDateTime> {
+        let present: HashSet<NaiveDateTime> = self
+            .series
+            .iter()
+            .filter_map(|elem| match elem.value {
+                GroupElement::DateTime(t) => Some(t),
+                _ => None,
+            })
+            .collect();
+        let (Some(&first), Some(&last)) = (present.iter().min(), present.iter().max()) else {
+            return Vec::new();
+        };
+        let step = chrono::Duration::seconds(i64::from(interval.grouping_secs()));
+
+        let mut missing = Vec::new();
+        let mut t = first;
+        while let Some(next) = t.checked_add_signed(step) {
+            if next >= last {
+                break;
+            }
+            if !present.contains(&next) {
+                missing.push(next);
+            }
+            t = next;
+        }
+        missing
+    }
 }
 
 macro_rules! min_max {
@@ -238,9 +616,11 @@ macro_rules! min_max {
 
 macro_rules! mean_deviation {
     ( $vf:expr, $t1:ty, $d:expr ) => {
-        let m = mean(&$vf);
-        $d.mean = Some(m);
-        $d.s_deviation = Some(population_standard_deviation(&$vf, Some(m)));
+        if !$vf.is_empty() {
+            let m = mean(&$vf);
+            $d.mean = Some(m);
+            $d.s_deviation = Some(population_standard_deviation(&$vf, Some(m)));
+        }
     };
 }
 
@@ -261,6 +641,7 @@ macro_rules! top_n {
                 count: *y,
             });
         }
+        $d.remaining_count = top_n_native[top_n_num..].iter().map(|(_, c)| c).sum();
         $d.mode = top_n.first().map(|v| v.value.clone());
         $d.top_n = top_n;
     };
@@ -282,6 +663,14 @@ pub(crate) fn describe(column: &Column, rows: &[usize], column_type: ColumnType)
             let f_values: Vec<f64> = iter.map(|v: i64| v as f64).collect();
             mean_deviation!(f_values, i64, description);
         }
+        ColumnType::UInt64 => {
+            let iter = column.primitive_iter::<UInt64Type>(rows).unwrap();
+            min_max!(iter, description, Element::UInt);
+            let iter = column.primitive_iter::<UInt64Type>(rows).unwrap();
+            #[allow(clippy::cast_precision_loss)] // 52-bit precision is good enough
+            let f_values: Vec<f64> = iter.map(|v: u64| v as f64).collect();
+            mean_deviation!(f_values, u64, description);
+        }
         ColumnType::Float64 => {
             let iter = column.primitive_iter::<Float64Type>(rows).unwrap();
             min_max!(iter, description, Element::Float);
@@ -295,6 +684,11 @@ pub(crate) fn describe(column: &Column, rows: &[usize], column_type: ColumnType)
     description
 }
 
+/// Counts the values of `column` in `rows`.
+///
+/// String and binary values are counted by reference into the Arrow buffers
+/// of `column`, so the frequency map takes a fixed amount of memory for each
+/// distinct value regardless of its length; only the top N are copied.
 #[must_use]
 pub(crate) fn n_largest_count(
     column: &Column,
@@ -316,7 +710,7 @@ pub(crate) fn n_largest_count(
                 number_of_top_n
             );
         }
-        ColumnType::Enum => {
+        ColumnType::UInt64 | ColumnType::Enum => {
             let iter = column.primitive_iter::<UInt64Type>(rows).unwrap();
             top_n!(
                 iter,
@@ -364,114 +758,82 @@ pub(crate) fn n_largest_count(
                 number_of_top_n
             );
         }
+        ColumnType::Boolean => {
+            let iter = column
+                .boolean_iter(rows)
+                .unwrap()
+                .map(|v| if v { "true" } else { "false" });
+            top_n!(
+                iter,
+                rows.len(),
+                n_largest_count,
+                &str,
+                Element::Enum,
+                number_of_top_n
+            );
+        }
         ColumnType::DateTime | ColumnType::Float64 => unreachable!(), // by implementation
     }
 
     n_largest_count
 }
 
+/// Returns the label of an enum value in `reverse_map`, joining multiple
+/// labels with `|`, or `unknown` if the value is not in the map. Without any
+/// map, the value itself is the label.
+pub(crate) fn enum_label(
+    reverse_map: &HashMap<u64, Vec<String>>,
+    value: u64,
+    unknown: &str,
+) -> String {
+    if reverse_map.is_empty() {
+        value.to_string()
+    } else {
+        reverse_map
+            .get(&value)
+            .map_or_else(|| unknown.to_string(), |labels| labels.join("|"))
+    }
+}
+
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub(crate) fn n_largest_count_enum(
     column: &Column,
     rows: &[usize],
     reverse_map: &HashMap<u64, Vec<String>>,
     number_of_top_n: u32,
+    unknown_label: &str,
 ) -> NLargestCount {
     let n_largest_count = n_largest_count(column, rows, ColumnType::Enum, number_of_top_n);
 
-    let (top_n, mode) = {
-        if reverse_map.is_empty() {
-            (
-                n_largest_count
-                    .top_n()
-                    .iter()
-                    .map(|elem| {
-                        if let Element::UInt(value) = elem.value {
-                            ElementCount {
-                                value: Element::Enum(value.to_string()),
-                                count: elem.count,
-                            }
-                        } else {
-                            ElementCount {
-                                value: Element::Enum("_N/A_".to_string()),
-                                count: elem.count,
-                            }
-                        }
-                    })
-                    .collect(),
-                match n_largest_count.mode() {
-                    Some(mode) => {
-                        if let Element::UInt(value) = mode {
-                            Some(Element::Enum(value.to_string()))
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                },
-            )
-        } else {
-            (
-                n_largest_count
-                    .top_n()
-                    .iter()
-                    .map(|elem| {
-                        if let Element::UInt(value) = elem.value {
-                            ElementCount {
-                                value: Element::Enum(reverse_map.get(&value).map_or(
-                                    "_NO_MAP_".to_string(),
-                                    |v| {
-                                        let mut s = String::new();
-                                        for (i, e) in v.iter().enumerate() {
-                                            s.push_str(e);
-                                            if i < v.len() - 1 {
-                                                s.push('|');
-                                            }
-                                        }
-                                        s
-                                    },
-                                )),
-                                count: elem.count,
-                            }
-                        } else {
-                            ElementCount {
-                                value: Element::Enum("_N/A_".to_string()),
-                                count: elem.count,
-                            }
-                        }
-                    })
-                    .collect(),
-                match n_largest_count.mode() {
-                    Some(mode) => {
-                        if let Element::UInt(value) = mode {
-                            Some(Element::Enum(reverse_map.get(value).map_or(
-                                "_NO_MAP_".to_string(),
-                                |v| {
-                                    let mut s = String::new();
-                                    for (i, e) in v.iter().enumerate() {
-                                        s.push_str(e);
-                                        if i < v.len() - 1 {
-                                            s.push('|');
-                                        }
-                                    }
-                                    s
-                                },
-                            )))
-                        } else {
-                            None
-                        }
-                    }
-                    None => None,
-                },
-            )
-        }
+    let top_n = n_largest_count
+        .top_n()
+        .iter()
+        .map(|elem| {
+            let label = if let Element::UInt(value) = elem.value {
+                enum_label(reverse_map, value, unknown_label)
+            } else {
+                "_N/A_".to_string()
+            };
+            ElementCount {
+                value: Element::Enum(label),
+                count: elem.count,
+            }
+        })
+        .collect();
+    let mode = match n_largest_count.mode() {
+        Some(Element::UInt(value)) => Some(Element::Enum(enum_label(
+            reverse_map,
+            *value,
+            unknown_label,
+        ))),
+        _ => None,
     };
 
     NLargestCount {
         number_of_elements: n_largest_count.number_of_elements(),
         top_n,
         mode,
+        remaining_count: n_largest_count.remaining_count(),
     }
 }
 
@@ -485,9 +847,10 @@ pub(crate) fn n_largest_count_float64(
     let mut n_largest_count = NLargestCount::default();
 
     let iter = column.primitive_iter::<Float64Type>(rows).unwrap();
-    let (rc, rt) = top_n_f64(iter, 10.0_f64.powi(precision), number_of_top_n);
+    let (rc, remaining, rt) = top_n_f64(iter, 10.0_f64.powi(precision), number_of_top_n);
     n_largest_count.number_of_elements = rc;
-    n_largest_count.mode = Some(rt[0].value.clone());
+    n_largest_count.remaining_count = remaining;
+    n_largest_count.mode = rt.first().map(|e| e.value.clone());
     n_largest_count.top_n = rt;
 
     n_largest_count
@@ -501,7 +864,7 @@ pub(crate) fn n_largest_count_datetime(
     number_of_top_n: u32,
 ) -> NLargestCount {
     let mut n_largest_count = NLargestCount::default();
-    let values = convert_time_intervals(column, rows, time_interval);
+    let values = convert_time_intervals(column, rows, Interval::clamped_secs(time_interval));
 
     top_n!(
         values.iter(),
@@ -515,6 +878,9 @@ pub(crate) fn n_largest_count_datetime(
     n_largest_count
 }
 
+/// Returns the start of the `interval` to which each timestamp of `rows`
+/// belongs, counting the intervals from the Unix epoch.
+///
 /// # Panics
 ///
 /// If `rows` contains an invalid timestamp in nanoseconds.
@@ -522,22 +888,10 @@ pub(crate) fn n_largest_count_datetime(
 pub(crate) fn convert_time_intervals(
     column: &Column,
     rows: &[usize],
-    time_interval: u32,
+    interval: Interval,
 ) -> Vec<NaiveDateTime> {
     const A_BILLION: i64 = 1_000_000_000;
-    let time_interval = if time_interval > MAX_TIME_INTERVAL {
-        MAX_TIME_INTERVAL
-    // Users want to see time series of the same order intervals within MAX_TIME_INTERVAL which is in date units.
-    // If the interval is larger than a day, it should be in date units.
-    } else {
-        time_interval
-    };
-    let time_interval = if time_interval < MIN_TIME_INTERVAL {
-        MIN_TIME_INTERVAL
-    } else {
-        time_interval
-    };
-    let time_interval = i64::from(time_interval);
+    let time_interval = i64::from(interval.grouping_secs());
 
     column
         .primitive_iter::<Int64Type>(rows)
@@ -567,11 +921,13 @@ where
     for (k, v) in &count {
         top_n.push(((*k).clone(), *v));
     }
-    top_n.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    top_n.sort_unstable_by_key(|v| std::cmp::Reverse(v.1));
     top_n
 }
 
-fn top_n_f64<I>(iter: I, precision: f64, number_of_top_n: u32) -> (usize, Vec<ElementCount>)
+/// Returns the number of distinct values, the total count of the values not
+/// in the top N, and the top N.
+fn top_n_f64<I>(iter: I, precision: f64, number_of_top_n: u32) -> (usize, usize, Vec<ElementCount>)
 where
     I: Iterator<Item = f64>,
 {
@@ -587,13 +943,16 @@ where
         .into_iter()
         .collect();
 
-    freqs.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    freqs.sort_unstable_by_key(|v| std::cmp::Reverse(v.1));
+    let number_of_top_n = number_of_top_n.to_usize().expect("safe: u32 -> usize");
+    let remaining = freqs.iter().skip(number_of_top_n).map(|(_, c)| c).sum();
 
     (
         freqs.len(),
+        remaining,
         freqs
             .into_iter()
-            .take(number_of_top_n.to_usize().expect("safe: u32 -> usize"))
+            .take(number_of_top_n)
             .map(|(v, count)| ElementCount {
                 value: Element::Float(v.into_inner()),
                 count,
@@ -631,7 +990,372 @@ mod tests {
     use super::*;
     use crate::Column;
     use arrow::datatypes::Int64Type;
-    use chrono::NaiveDate;
+    use chrono::{NaiveDate, Timelike};
+
+    #[test]
+    fn binary_encoding() {
+        let element = Element::Binary(vec![0x0a, 0xff, 0x00]);
+        assert_eq!(element.to_string(), "0aff00");
+        assert_eq!(element.to_string_with(BinaryEncoding::LowerHex), "0aff00");
+        assert_eq!(element.to_string_with(BinaryEncoding::UpperHex), "0AFF00");
+        assert_eq!(element.to_string_with(BinaryEncoding::Base64), "Cv8A");
+        assert_eq!(Element::Binary(Vec::new()).to_string(), "");
+        assert_eq!(
+            Element::Int(-3).to_string_with(BinaryEncoding::Base64),
+            "-3"
+        );
+    }
+
+    #[test]
+    fn merge_statistics() {
+        use crate::{ColumnType, Table};
+        use arrow::datatypes::Schema;
+        use std::sync::Arc;
+
+        let values: Vec<i64> = vec![1, 3, 3, 5, 2, 1, 3, 8];
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![column], HashMap::new())
+            .expect("invalid columns");
+        let statistics = |rows: &[usize]| {
+            table
+                .statistics(
+                    rows,
+                    &Arc::new(vec![ColumnType::Int64]),
+                    &HashMap::new(),
+                    &Arc::new(Vec::new()),
+                    &Arc::new(vec![10]),
+                    2,
+                )
+                .unwrap()
+                .remove(0)
+        };
+
+        let mut merged = statistics(&[0, 1, 2, 3]);
+        merged.merge(&statistics(&[4, 5, 6, 7]), 10);
+        let union = statistics(&[0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let (m, u) = (&merged.description, &union.description);
+        assert_eq!(m.count(), u.count());
+        assert_eq!(m.min(), u.min());
+        assert_eq!(m.max(), u.max());
+        assert!((m.mean().unwrap() - u.mean().unwrap()).abs() < 1e-9);
+        assert!((m.std_deviation().unwrap() - u.std_deviation().unwrap()).abs() < 1e-9);
+
+        let sorted = |n: &NLargestCount| {
+            let mut top_n: Vec<_> = n
+                .top_n()
+                .iter()
+                .map(|e| (e.count, e.value.to_string()))
+                .collect();
+            top_n.sort();
+            top_n
+        };
+        let (m, u) = (&merged.n_largest_count, &union.n_largest_count);
+        assert_eq!(m.number_of_elements(), u.number_of_elements());
+        assert_eq!(sorted(m), sorted(u));
+        assert_eq!(m.mode(), Some(&Element::Int(3)));
+        assert_eq!(m.remaining_count(), 0);
+    }
+
+    #[test]
+    fn merge_full_top_n() {
+        let values: Vec<i64> = vec![1, 1, 1, 2, 2, 3, 4, 5, 5, 5, 5, 2, 2, 6];
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let first = [0, 1, 2, 3, 4, 5, 6];
+        let second = [7, 8, 9, 10, 11, 12, 13];
+        let mut merged = n_largest_count(&column, &first, ColumnType::Int64, 3);
+        assert_eq!(merged.top_n().len(), 3);
+        merged.merge(&n_largest_count(&column, &second, ColumnType::Int64, 3), 3);
+
+        assert_eq!(
+            merged.top_n().iter().map(|e| e.count).collect::<Vec<_>>(),
+            [4, 4, 3]
+        );
+        assert_eq!(merged.remaining_count(), 3);
+        assert_eq!(merged.number_of_elements(), 5);
+        assert_eq!(merged.mode(), Some(&Element::Int(2)));
+
+        let mut merged = n_largest_count(&column, &first[..3], ColumnType::Int64, 2);
+        merged.merge(&n_largest_count(&column, &second, ColumnType::Int64, 2), 2);
+        assert_eq!(
+            merged.top_n().iter().map(|e| e.count).collect::<Vec<_>>(),
+            [4, 3]
+        );
+        assert_eq!(merged.number_of_elements(), 3);
+        assert_eq!(merged.remaining_count(), 3);
+    }
+
+    #[test]
+    fn prometheus_metrics() {
+        let values: Vec<i64> = vec![4, 1, 4, 7];
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let rows = [0, 1, 2, 3];
+        let statistics = ColumnStatistics {
+            description: describe(&column, &rows, ColumnType::Int64),
+            n_largest_count: n_largest_count(&column, &rows, ColumnType::Int64, 10),
+        };
+        assert_eq!(
+            ColumnStatistics::to_prometheus("flows", [("bytes", &statistics)]),
+            "# TYPE structured_column_count gauge\n\
+             structured_column_count{table=\"flows\",column=\"bytes\"} 4\n\
+             # TYPE structured_column_distinct gauge\n\
+             structured_column_distinct{table=\"flows\",column=\"bytes\"} 3\n\
+             # TYPE structured_column_min gauge\n\
+             structured_column_min{table=\"flows\",column=\"bytes\"} 1\n\
+             # TYPE structured_column_max gauge\n\
+             structured_column_max{table=\"flows\",column=\"bytes\"} 7\n\
+             # TYPE structured_column_mean gauge\n\
+             structured_column_mean{table=\"flows\",column=\"bytes\"} 4\n"
+        );
+
+        let other = ColumnStatistics {
+            description: Description::new(2, None, None, None, None),
+            n_largest_count: NLargestCount::new(2, Vec::new(), None),
+        };
+        assert_eq!(
+            ColumnStatistics::to_prometheus("t", [("a\"b", &other)]),
+            "# TYPE structured_column_count gauge\n\
+             structured_column_count{table=\"t\",column=\"a\\\"b\"} 2\n\
+             # TYPE structured_column_distinct gauge\n\
+             structured_column_distinct{table=\"t\",column=\"a\\\"b\"} 2\n"
+        );
+
+        // One header per family for multiple columns
+        let rendered = ColumnStatistics::to_prometheus("t", [("x", &statistics), ("y", &other)]);
+        assert_eq!(
+            rendered.matches("# TYPE structured_column_count").count(),
+            1
+        );
+        assert!(rendered.starts_with(
+            "# TYPE structured_column_count gauge\n\
+             structured_column_count{table=\"t\",column=\"x\"} 4\n\
+             structured_column_count{table=\"t\",column=\"y\"} 2\n\
+             # TYPE structured_column_distinct gauge\n"
+        ));
+        assert!(ColumnStatistics::to_prometheus("t", []).is_empty());
+
+        let statistics = ColumnStatistics {
+            description: Description::new(
+                3,
+                Some(f64::NAN),
+                None,
+                Some(Element::Float(f64::NEG_INFINITY)),
+                Some(Element::Float(f64::INFINITY)),
+            ),
+            n_largest_count: NLargestCount::default(),
+        };
+        let rendered = ColumnStatistics::to_prometheus("t", [("f", &statistics)]);
+        assert!(rendered.contains("structured_column_min{table=\"t\",column=\"f\"} -Inf\n"));
+        assert!(rendered.contains("structured_column_max{table=\"t\",column=\"f\"} +Inf\n"));
+        assert!(rendered.contains("structured_column_mean{table=\"t\",column=\"f\"} NaN\n"));
+    }
+
+    #[test]
+    fn remaining_count() {
+        let values: Vec<i64> = vec![1, 3, 3, 5, 2, 1, 3, 8, 3, 1];
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let rows: Vec<usize> = (0..values.len()).collect();
+
+        let n_largest = n_largest_count(&column, &rows, ColumnType::Int64, 2);
+        assert_eq!(n_largest.number_of_elements(), 5);
+        assert_eq!(n_largest.top_n().len(), 2);
+        assert_eq!(n_largest.top_n()[0].count, 4);
+        assert_eq!(n_largest.top_n()[1].count, 3);
+        assert_eq!(n_largest.remaining_count(), 3);
+
+        let n_largest = n_largest_count(&column, &rows, ColumnType::Int64, 10);
+        assert_eq!(n_largest.number_of_elements(), 5);
+        assert_eq!(n_largest.remaining_count(), 0);
+
+        let values: Vec<f64> = vec![1.0, 1.001, 2.0, 3.0, 3.0];
+        let column = Column::try_from_slice::<Float64Type>(&values).unwrap();
+        let n_largest = n_largest_count_float64(&column, &[0, 1, 2, 3, 4], 1, 2);
+        assert_eq!(n_largest.number_of_elements(), 3);
+        assert_eq!(n_largest.remaining_count(), 3);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn uint64_statistics() {
+        let ports: Vec<u64> = vec![443, 80, 443, 8080, 22, 443];
+        let column = Column::try_from_slice::<UInt64Type>(&ports).unwrap();
+        let rows: Vec<usize> = (0..ports.len()).collect();
+
+        let description = describe(&column, &rows, ColumnType::UInt64);
+        assert_eq!(description.min(), Some(&Element::UInt(22)));
+        assert_eq!(description.max(), Some(&Element::UInt(8080)));
+        assert_eq!(description.mean(), Some(9511.0 / 6.0));
+        assert!(description.std_deviation().is_some());
+
+        let n_largest = n_largest_count(&column, &rows, ColumnType::UInt64, 1);
+        assert_eq!(n_largest.number_of_elements(), 4);
+        assert_eq!(n_largest.top_n()[0].value, Element::UInt(443));
+        assert_eq!(n_largest.top_n()[0].count, 3);
+        assert_eq!(n_largest.mode(), Some(&Element::UInt(443)));
+
+        let description = describe(&column, &rows, ColumnType::Enum);
+        assert_eq!(description.min(), None);
+        assert_eq!(description.mean(), None);
+
+        assert_eq!("uint64".parse::<ColumnType>(), Ok(ColumnType::UInt64));
+    }
+
+    #[test]
+    fn n_largest_count_long_strings() {
+        use arrow::array::{Array, StringArray};
+        use std::sync::Arc;
+
+        let long = |c: char| format!("https://example.com/{}", c.to_string().repeat(10_000));
+        let (a, b, c) = (long('a'), long('b'), long('c'));
+        // `b` shares a long prefix with `bb`, which must be counted separately.
+        let bb = format!("{b}b");
+        let values = [&a, &b, &a, &c, &bb, &a, &b];
+        let array: Arc<dyn Array> = Arc::new(StringArray::from(
+            values.iter().map(|v| v.as_str()).collect::<Vec<_>>(),
+        ));
+        let column = Column::from(array);
+        let rows: Vec<usize> = (0..values.len()).collect();
+
+        let n_largest = n_largest_count(&column, &rows, ColumnType::Utf8, 2);
+        assert_eq!(n_largest.number_of_elements(), 4);
+        assert_eq!(n_largest.top_n()[0].value, Element::Text(a.clone()));
+        assert_eq!(n_largest.top_n()[0].count, 3);
+        assert_eq!(n_largest.top_n()[1].value, Element::Text(b.clone()));
+        assert_eq!(n_largest.top_n()[1].count, 2);
+        assert_eq!(n_largest.remaining_count(), 2);
+        assert_eq!(n_largest.mode(), Some(&Element::Text(a)));
+    }
+
+    #[test]
+    fn split_group_count_series() {
+        let series: Vec<_> = [(1, 3), (2, 5), (4, 1)]
+            .into_iter()
+            .map(|(value, count)| GroupElementCount {
+                value: GroupElement::Int(value),
+                count,
+            })
+            .collect();
+        let group_count = GroupCount {
+            count_index: None,
+            series: series.clone(),
+        };
+        let (values, counts) = group_count.split_series();
+        assert_eq!(
+            values,
+            vec![
+                GroupElement::Int(1),
+                GroupElement::Int(2),
+                GroupElement::Int(4)
+            ]
+        );
+        assert_eq!(counts, vec![3, 5, 1]);
+        assert!(values
+            .into_iter()
+            .zip(counts)
+            .zip(series)
+            .all(|((value, count), elem)| value == elem.value && count == elem.count));
+    }
+
+    #[test]
+    fn group_element_display_string() {
+        let reverse_map: HashMap<u64, Vec<String>> =
+            [(6, vec!["tcp".to_string()])].into_iter().collect();
+        let datetime = NaiveDate::from_ymd_opt(2020, 1, 2)
+            .unwrap()
+            .and_hms_milli_opt(3, 4, 5, 600)
+            .unwrap();
+        let cases = [
+            (GroupElement::Int(-42), None, "-42"),
+            (GroupElement::UInt(6), None, "6"),
+            (GroupElement::UInt(6), Some(&reverse_map), "tcp"),
+            (GroupElement::UInt(17), Some(&reverse_map), "_NO_MAP_"),
+            (GroupElement::Enum("udp".to_string()), None, "udp"),
+            (GroupElement::Text("a b".to_string()), None, "a b"),
+            (
+                GroupElement::IpAddr(Ipv4Addr::new(192, 168, 0, 1).into()),
+                None,
+                "192.168.0.1",
+            ),
+            (
+                GroupElement::DateTime(datetime),
+                None,
+                "2020-01-02T03:04:05.600",
+            ),
+            (
+                GroupElement::DateTime(datetime.with_nanosecond(0).unwrap()),
+                None,
+                "2020-01-02T03:04:05",
+            ),
+        ];
+        for (elem, map, expected) in cases {
+            assert_eq!(elem.to_display_string(map), expected);
+        }
+    }
+
+    #[test]
+    fn missing_intervals() {
+        let hour = |h| {
+            NaiveDate::from_ymd_opt(2020, 1, 1)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let group_count = GroupCount {
+            count_index: None,
+            series: [0, 1, 3, 4]
+                .into_iter()
+                .map(|h| GroupElementCount {
+                    value: GroupElement::DateTime(hour(h)),
+                    count: 1,
+                })
+                .collect(),
+        };
+        assert_eq!(
+            group_count.missing_intervals(Interval::Hours(1)),
+            vec![hour(2)]
+        );
+        assert!(group_count.missing_intervals(Interval::Hours(3)).is_empty());
+        assert_eq!(
+            group_count.missing_intervals(Interval::Minutes(90)).len(),
+            1
+        );
+        assert_eq!(
+            group_count.missing_intervals(Interval::Seconds(0)),
+            group_count.missing_intervals(Interval::Seconds(30))
+        );
+        // Every 30 seconds between hours 0 and 4, except hours 1 and 3
+        assert_eq!(
+            group_count.missing_intervals(Interval::Seconds(0)).len(),
+            477
+        );
+
+        let group_count = GroupCount {
+            count_index: None,
+            series: Vec::new(),
+        };
+        assert!(group_count.missing_intervals(Interval::Hours(1)).is_empty());
+
+        let day = |d| {
+            NaiveDate::from_ymd_opt(2020, 1, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let group_count = GroupCount {
+            count_index: None,
+            series: [1, 3, 7]
+                .into_iter()
+                .map(|d| GroupElementCount {
+                    value: GroupElement::DateTime(day(d)),
+                    count: 1,
+                })
+                .collect(),
+        };
+        assert_eq!(
+            group_count.missing_intervals(Interval::Days(2)),
+            vec![day(5)]
+        );
+    }
 
     #[test]
     fn test_convert_time_intervals() {
@@ -688,7 +1412,7 @@ mod tests {
         ];
         let c4 = Column::try_from_slice::<Int64Type>(&c4_v).unwrap();
         let rows = vec![0_usize, 3, 1, 4, 2, 6, 5];
-        let time_interval = 3600;
+        let time_interval = Interval::Hours(1);
         let rst = convert_time_intervals(&c4, &rows, time_interval);
         assert_eq!(rst.len(), 7);
         assert_eq!(
@@ -745,7 +1469,7 @@ mod tests {
         ];
         let c4 = Column::try_from_slice::<Int64Type>(&c4_v).unwrap();
         let rows = vec![0_usize, 1, 2, 3];
-        let time_interval = 3600;
+        let time_interval = Interval::Hours(1);
         let rst = convert_time_intervals(&c4, &rows, time_interval);
         let converted = [
             NaiveDate::from_ymd_opt(2019, 9, 22)
@@ -769,7 +1493,7 @@ mod tests {
             assert_eq!(rst.get(seq), Some(c));
         }
 
-        let time_interval = 600;
+        let time_interval = Interval::Minutes(10);
         let rst = convert_time_intervals(&c4, &rows, time_interval);
         let converted = [
             NaiveDate::from_ymd_opt(2019, 9, 22)
@@ -793,4 +1517,32 @@ mod tests {
             assert_eq!(rst.get(seq), Some(c));
         }
     }
+
+    #[test]
+    fn degenerate_time_intervals() {
+        let time = |h, m, s| {
+            NaiveDate::from_ymd_opt(2019, 9, 22)
+                .unwrap()
+                .and_hms_opt(h, m, s)
+                .unwrap()
+        };
+        let c4_v: Vec<i64> = [time(6, 10, 11), time(6, 10, 45), time(23, 59, 59)]
+            .iter()
+            .map(|t| t.and_utc().timestamp_nanos_opt().unwrap())
+            .collect();
+        let c4 = Column::try_from_slice::<Int64Type>(&c4_v).unwrap();
+        let rows = vec![0_usize, 1, 2];
+
+        let min = Interval::Seconds(MIN_TIME_INTERVAL);
+        let rst = convert_time_intervals(&c4, &rows, Interval::Seconds(0));
+        assert_eq!(rst, [time(6, 10, 0), time(6, 10, 30), time(23, 59, 30)]);
+        assert_eq!(rst, convert_time_intervals(&c4, &rows, min));
+        let rst = convert_time_intervals(&c4, &rows, Interval::clamped_secs(0));
+        assert_eq!(rst, convert_time_intervals(&c4, &rows, min));
+
+        let rst = convert_time_intervals(&c4, &rows, Interval::clamped_secs(u32::MAX));
+        assert_eq!(rst, [time(0, 0, 0); 3]);
+        let day = Interval::Seconds(MAX_TIME_INTERVAL);
+        assert_eq!(rst, convert_time_intervals(&c4, &rows, day));
+    }
 }
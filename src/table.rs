@@ -1,54 +1,236 @@
 use arrow::array::{
-    Array, BinaryArray, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
+    make_comparator, new_empty_array, Array, BinaryArray, BooleanArray, DictionaryArray,
+    DynComparator, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
     PrimitiveArray, PrimitiveBuilder, StringArray, UInt16Array, UInt32Array, UInt64Array,
     UInt8Array,
 };
-use arrow::datatypes::{ArrowPrimitiveType, DataType, Int64Type, Schema, TimeUnit};
+use arrow::buffer::BooleanBuffer;
+use arrow::compute::kernels::cmp;
+use arrow::compute::SortOptions;
+use arrow::datatypes::{
+    ArrowNativeTypeOp, ArrowPrimitiveType, DataType, Field, Float64Type, Int64Type, Schema,
+    TimeUnit, UInt64Type,
+};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use chrono::NaiveDateTime;
 use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
 use std::iter::{Flatten, Iterator};
 use std::marker::PhantomData;
+use std::net::Ipv4Addr;
+use std::path::Path;
 use std::slice;
 use std::sync::Arc;
 use std::vec;
-use strum_macros::EnumString;
+use strum_macros::{EnumString, IntoStaticStr};
+use thiserror::Error;
 
+use crate::csv::{FieldParser, Reader};
 use crate::stats::{
-    convert_time_intervals, describe, n_largest_count, n_largest_count_datetime,
-    n_largest_count_enum, n_largest_count_float64, ColumnStatistics, GroupCount, GroupElement,
-    GroupElementCount,
+    convert_time_intervals, describe, enum_label, n_largest_count, n_largest_count_datetime,
+    n_largest_count_enum, n_largest_count_float64, BinaryEncoding, ColumnStatistics, Element,
+    GroupCount, GroupElement, GroupElementCount, Interval, UNKNOWN_ENUM_LABEL,
 };
 
 type ReverseEnumMaps = HashMap<usize, HashMap<u64, Vec<String>>>;
+type Cells<'a> = Box<dyn Iterator<Item = Option<Element>> + 'a>;
 /// The data type of a table column.
-#[derive(Clone, Copy, Debug, Deserialize, EnumString, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, EnumString, Eq, IntoStaticStr, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "snake_case")]
 pub enum ColumnType {
     Int64,
+    /// An unsigned integer, such as a port number, treated as a number
+    /// rather than an enum id.
+    #[strum(serialize = "uint64")]
+    UInt64,
     Float64,
     DateTime,
     IpAddr,
     Enum,
     Utf8,
     Binary,
+    Boolean,
 }
 
 impl From<ColumnType> for DataType {
-    #[must_use]
     fn from(ct: ColumnType) -> Self {
         match ct {
             ColumnType::Int64 => Self::Int64,
+            ColumnType::UInt64 => Self::UInt64,
             ColumnType::Float64 => Self::Float64,
             ColumnType::DateTime => Self::Timestamp(TimeUnit::Second, None),
             ColumnType::Enum | ColumnType::Utf8 => Self::Utf8,
             ColumnType::IpAddr => Self::UInt32,
             ColumnType::Binary => Self::Binary,
+            ColumnType::Boolean => Self::Boolean,
+        }
+    }
+}
+
+impl ColumnType {
+    /// The key of the `Field` metadata in which [`ColumnType::annotate`]
+    /// records the `ColumnType` of a column.
+    pub const METADATA_KEY: &'static str = "column_type";
+
+    /// Records this `ColumnType` in the metadata of `field`, e.g., to tell an
+    /// enum column from a numeric one when both are stored as `UInt64`.
+    #[must_use]
+    pub fn annotate(self, field: Field) -> Field {
+        let mut metadata = field.metadata().clone();
+        metadata.insert(
+            Self::METADATA_KEY.to_string(),
+            <&str>::from(self).to_string(),
+        );
+        field.with_metadata(metadata)
+    }
+
+    /// Returns the `ColumnType` recorded in the metadata of `field` by
+    /// [`ColumnType::annotate`], or else the one of a column stored as the
+    /// data type of `field`. Returns `None` if neither is known.
+    #[must_use]
+    pub fn of_field(field: &Field) -> Option<Self> {
+        field
+            .metadata()
+            .get(Self::METADATA_KEY)
+            .and_then(|t| t.parse().ok())
+            .or_else(|| Self::from_stored(field.data_type()))
+    }
+
+    /// Returns the `ColumnType` of a table column stored as `data_type`, or
+    /// `None` if there is no such `ColumnType`.
+    ///
+    /// Table columns store IP addresses as `UInt32` and enum ids as `UInt64`,
+    /// so `UInt64` is taken as `Enum` rather than `ColumnType::UInt64`.
+    /// Narrower signed integers are `Int64`, and `Float32` is `Float64`, as
+    /// their statistics are computed over the values widened to `i64` or
+    /// `f64`.
+    fn from_stored(data_type: &DataType) -> Option<Self> {
+        match data_type {
+            DataType::Int64 | DataType::Int32 | DataType::Int16 | DataType::Int8 => {
+                Some(Self::Int64)
+            }
+            DataType::Float64 | DataType::Float32 => Some(Self::Float64),
+            DataType::Timestamp(_, _) => Some(Self::DateTime),
+            DataType::UInt32 => Some(Self::IpAddr),
+            DataType::UInt64 => Some(Self::Enum),
+            DataType::Utf8 => Some(Self::Utf8),
+            DataType::Binary => Some(Self::Binary),
+            DataType::Boolean => Some(Self::Boolean),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DataType` in which table columns store values of this
+    /// `ColumnType`, the inverse of [`ColumnType::from_stored`].
+    fn stored_data_type(self) -> DataType {
+        match self {
+            Self::DateTime => DataType::Int64,
+            Self::Enum => DataType::UInt64,
+            _ => self.into(),
         }
     }
 }
 
+/// The error type for operations on a `Table`.
+#[derive(Debug, Error)]
+pub enum TableError {
+    #[error("arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+    #[error("column index {0} is out of range")]
+    ColumnIndexOutOfRange(usize),
+    #[error("column {0} has a different type from that of the other table")]
+    IncompatibleColumn(usize),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("expected {expected} columns, but found {found}")]
+    NumColumnsMismatch { expected: usize, found: usize },
+    #[error("columns {0} and {1} have different types")]
+    TypeMismatch(usize, usize),
+    #[error("column {0} is not of the expected type")]
+    UnexpectedType(usize),
+    #[error("fraction {0} is not between 0 and 1")]
+    InvalidFraction(f64),
+    #[error("expected {expected} numbers of top N, one per column, but found {found}")]
+    NumTopNMismatch { expected: usize, found: usize },
+    #[error("expected {expected} time intervals, one per DateTime column, but found {found}")]
+    NumTimeIntervalsMismatch { expected: usize, found: usize },
+}
+
+/// A line of the output of [`Table::write_statistics_ndjson`].
+#[derive(Serialize)]
+struct StatisticsLine<'a> {
+    column: usize,
+    name: Option<&'a str>,
+    #[serde(rename = "type")]
+    column_type: ColumnType,
+    #[serde(flatten)]
+    statistics: &'a ColumnStatistics,
+}
+
+/// Returns `true` if the values of a field of `field_type` are stored in a
+/// column of `stored_type`. Timestamps are stored as `Int64`.
+fn is_stored_as(field_type: &DataType, stored_type: &DataType) -> bool {
+    match field_type {
+        DataType::Timestamp(_, _) => stored_type == &DataType::Int64,
+        data_type => data_type == stored_type,
+    }
+}
+
+/// Comparisons of `Schema`s by their fields, ignoring nullability and
+/// metadata.
+pub trait SchemaExt {
+    /// Returns `true` if both schemas have the same data types in the same
+    /// order, regardless of the field names.
+    fn types_equal(&self, other: &Schema) -> bool;
+
+    /// Returns `true` if both schemas have the same field names and data
+    /// types in the same order.
+    fn fields_equal(&self, other: &Schema) -> bool;
+}
+
+impl SchemaExt for Schema {
+    fn types_equal(&self, other: &Schema) -> bool {
+        self.fields().len() == other.fields().len() && first_mismatch(self, other, false).is_none()
+    }
+
+    fn fields_equal(&self, other: &Schema) -> bool {
+        self.fields().len() == other.fields().len() && first_mismatch(self, other, true).is_none()
+    }
+}
+
+/// Returns the index of the first field that differs in `a` and `b`,
+/// comparing the data types and, if `match_names` is `true`, the names.
+fn first_mismatch(a: &Schema, b: &Schema, match_names: bool) -> Option<usize> {
+    a.fields()
+        .iter()
+        .zip(b.fields())
+        .position(|(x, y)| x.data_type() != y.data_type() || (match_names && x.name() != y.name()))
+}
+
+/// The differences of an incoming schema from that of a `Table`, matched by
+/// field name.
+#[derive(Debug, Default, Eq, Error, PartialEq)]
+#[error(
+    "schema drift: {} added, {} removed, {} retyped",
+    .added.len(),
+    .removed.len(),
+    .retyped.len()
+)]
+pub struct SchemaDrift {
+    /// The names of the fields only in the incoming schema.
+    pub added: Vec<String>,
+    /// The names of the fields missing from the incoming schema.
+    pub removed: Vec<String>,
+    /// The name, the expected type, and the incoming type of each field
+    /// whose type changed.
+    pub retyped: Vec<(String, DataType, DataType)>,
+}
+
 /// Structured data represented in a column-oriented form.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -56,6 +238,7 @@ pub struct Table<I> {
     schema: Arc<Schema>,
     columns: Vec<Column>,
     event_ids: HashMap<I, usize>,
+    max_arrays_per_column: Option<usize>,
 }
 
 impl<I> Table<I>
@@ -79,6 +262,7 @@ where
                 schema,
                 columns,
                 event_ids: HashMap::new(),
+                max_arrays_per_column: None,
             });
         };
         if columns.iter().skip(1).all(|c| c.len() == len) {
@@ -86,26 +270,226 @@ where
                 schema,
                 columns,
                 event_ids,
+                max_arrays_per_column: None,
             })
         } else {
             Err("columns must have the same length")
         }
     }
 
-    /// Moves all the rows of `other` intot `self`, leaving `other` empty.
+    /// Reads CSV files that together form one table, in the order of `paths`.
+    ///
+    /// Each file is read as a stream of records, parsed by `parsers` as in
+    /// [`Reader`]; a quoted field may span lines. The event ids are the
+    /// values of column `event_id_column` of type `T`, as in
+    /// [`Table::rebuild_event_ids`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of parsers differs from that of the
+    /// fields in `schema`, a parser does not produce the data type of its
+    /// field, `event_id_column` is out of range or not of type `T`, or reading
+    /// or parsing a file fails.
+    pub fn from_csv_paths<T, P>(
+        schema: Arc<Schema>,
+        paths: &[P],
+        parsers: &[FieldParser],
+        batch_size: usize,
+        event_id_column: usize,
+    ) -> Result<Self, TableError>
+    where
+        T: ArrowPrimitiveType,
+        I: From<T::Native>,
+        P: AsRef<Path>,
+    {
+        if parsers.len() != schema.fields().len() {
+            return Err(TableError::NumColumnsMismatch {
+                expected: schema.fields().len(),
+                found: parsers.len(),
+            });
+        }
+        if event_id_column >= parsers.len() {
+            return Err(TableError::ColumnIndexOutOfRange(event_id_column));
+        }
+        let mut columns: Vec<Column> = Reader::new(std::iter::empty(), batch_size, parsers)
+            .generate_empty_batch()
+            .columns()
+            .iter()
+            .map(|array| Column::from(array.clone()))
+            .collect();
+        for (index, (field, column)) in schema.fields().iter().zip(&columns).enumerate() {
+            if !column
+                .data_type()
+                .is_some_and(|parsed| is_stored_as(field.data_type(), parsed))
+            {
+                return Err(TableError::UnexpectedType(index));
+            }
+        }
+        for path in paths {
+            let mut input = std::io::BufReader::new(std::fs::File::open(path)?);
+            let mut reader = Reader::new(std::iter::empty(), batch_size, parsers);
+            while let Some(batch) = reader.next_batch_from_buf(&mut input)? {
+                for (index, (column, array)) in columns.iter_mut().zip(batch.columns()).enumerate()
+                {
+                    column
+                        .try_append(&mut Column::from(array.clone()))
+                        .map_err(|_| TableError::IncompatibleColumn(index))?;
+                }
+            }
+        }
+        let mut table = Self {
+            schema,
+            columns,
+            event_ids: HashMap::new(),
+            max_arrays_per_column: None,
+        };
+        table.rebuild_event_ids::<T>(event_id_column)?;
+        Ok(table)
+    }
+
+    /// Checks whether data with `batch_schema` can be appended to this table,
+    /// comparing fields by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SchemaDrift` describing the added, removed, and retyped
+    /// fields if the schemas differ.
+    pub fn check_compatible(&self, batch_schema: &Schema) -> Result<(), SchemaDrift> {
+        let mut drift = SchemaDrift::default();
+        for field in self.schema.fields() {
+            match batch_schema.field_with_name(field.name()) {
+                Ok(other) if other.data_type() != field.data_type() => drift.retyped.push((
+                    field.name().clone(),
+                    field.data_type().clone(),
+                    other.data_type().clone(),
+                )),
+                Ok(_) => {}
+                Err(_) => drift.removed.push(field.name().clone()),
+            }
+        }
+        for field in batch_schema.fields() {
+            if self.schema.field_with_name(field.name()).is_err() {
+                drift.added.push(field.name().clone());
+            }
+        }
+
+        if drift == SchemaDrift::default() {
+            Ok(())
+        } else {
+            Err(drift)
+        }
+    }
+
+    /// Moves all the rows of `other` into `self`, leaving `other` empty.
+    ///
+    /// All the columns are checked before any of them is modified, so `self`
+    /// is left unchanged if this panics.
     ///
     /// # Panics
     ///
-    /// Panics if the types of columns are different, or the number of rows
-    /// overflows `usize`.
+    /// Panics if the numbers or the types of columns are different, or the
+    /// number of rows overflows `usize`.
     pub fn append(&mut self, other: &mut Self) {
+        if let Err(e) = self.check_appendable(other) {
+            panic!("cannot append the table: {e}");
+        }
+        self.append_columns(other);
+    }
+
+    /// Limits the number of arrays each column may hold. When appending
+    /// another table makes a column hold more than `max` arrays, its arrays
+    /// are concatenated into one. A `max` of zero is treated as one.
+    #[must_use]
+    pub fn with_max_arrays_per_column(mut self, max: usize) -> Self {
+        self.max_arrays_per_column = Some(max.max(1));
+        self
+    }
+
+    /// Moves the columns of `other` into `self`, which must have been checked
+    /// by `check_appendable`.
+    fn append_columns(&mut self, other: &mut Self) {
         for (self_col, other_col) in self.columns.iter_mut().zip(other.columns.iter_mut()) {
-            self_col.append(other_col);
+            self_col
+                .try_append(other_col)
+                .expect("columns of the same type should be appended");
+            if self
+                .max_arrays_per_column
+                .is_some_and(|max| self_col.arrays.len() > max)
+            {
+                self_col
+                    .compact()
+                    .expect("arrays of the same type should be concatenated");
+            }
+        }
+    }
+
+    /// Splits the arrays of every column anew into arrays of `chunk_size`
+    /// rows, except the last, which may be shorter, so that the arrays of all
+    /// the columns start at the same rows. A `chunk_size` of zero
+    /// concatenates each column into one array. The values are unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if concatenating the arrays of a column fails. The
+    /// columns before it have been rechunked in that case.
+    pub fn rechunk(&mut self, chunk_size: usize) -> Result<(), TableError> {
+        for column in &mut self.columns {
+            column.rechunk(chunk_size)?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the columns of `other` can be appended to those of
+    /// `self`: the numbers of columns are the same, the columns of `other`
+    /// have the same length, and each pair of columns has the same type.
+    fn check_appendable(&self, other: &Self) -> Result<(), TableError> {
+        let (expected, found) = (self.columns.len(), other.columns.len());
+        if expected != found {
+            return Err(TableError::NumColumnsMismatch { expected, found });
+        }
+        let other_rows = other.num_rows();
+        if self.num_rows().checked_add(other_rows).is_none() {
+            return Err(TableError::Arrow(ArrowError::ComputeError(
+                "number of rows overflows usize".to_string(),
+            )));
+        }
+        for (index, (self_col, other_col)) in self.columns.iter().zip(&other.columns).enumerate() {
+            if other_col.len() != other_rows {
+                return Err(TableError::IncompatibleColumn(index));
+            }
+            if let (Some(s), Some(o)) = (self_col.data_type(), other_col.data_type()) {
+                if s != o {
+                    return Err(TableError::IncompatibleColumn(index));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Moves all the rows of `other` into `self`, like [`Table::append`], if
+    /// the schemas of both tables have the same data types, and the same
+    /// field names if `match_names` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schemas have different numbers of fields, a
+    /// field differs, or the columns cannot be appended as in
+    /// [`Table::append`]. Neither table is modified in that case.
+    pub fn try_append(&mut self, other: &mut Self, match_names: bool) -> Result<(), TableError> {
+        let (expected, found) = (self.schema.fields().len(), other.schema.fields().len());
+        if expected != found {
+            return Err(TableError::NumColumnsMismatch { expected, found });
+        }
+        if let Some(index) = first_mismatch(&self.schema, &other.schema, match_names) {
+            return Err(TableError::IncompatibleColumn(index));
         }
+        self.check_appendable(other)?;
+        self.append_columns(other);
+        Ok(())
     }
 
     /// Returns an `Iterator` for columns.
-    pub fn columns(&self) -> slice::Iter<Column> {
+    pub fn columns(&self) -> slice::Iter<'_, Column> {
         self.columns.iter()
     }
 
@@ -115,6 +499,77 @@ where
         self.columns.get(i)
     }
 
+    /// Returns the index of the column whose field in the schema is named
+    /// `name`. If more than one field has the name, the first one wins.
+    #[must_use]
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.schema
+            .fields()
+            .iter()
+            .take(self.columns.len())
+            .position(|field| field.name() == name)
+    }
+
+    /// Returns the column whose field in the schema is named `name`, like
+    /// [`Table::column_index`].
+    #[must_use]
+    pub fn column_by_name(&self, name: &str) -> Option<&Column> {
+        self.columns.get(self.column_index(name)?)
+    }
+
+    /// Returns the column at `index` tagged with its type in the schema, or
+    /// `None` if there is no such column or its type is not supported by
+    /// `TypedColumn`.
+    #[must_use]
+    pub fn typed_column(&self, index: usize) -> Option<TypedColumn<'_>> {
+        let column = self.columns.get(index)?;
+        let typed = match self.schema.fields().get(index)?.data_type() {
+            DataType::Int64 => TypedColumn::Int64(column),
+            DataType::Float64 => TypedColumn::Float64(column),
+            DataType::Timestamp(_, _) => TypedColumn::DateTime(column),
+            DataType::UInt32 => TypedColumn::UInt32(column),
+            DataType::UInt64 => TypedColumn::UInt64(column),
+            DataType::Utf8 => TypedColumn::Utf8(column),
+            DataType::Binary => TypedColumn::Binary(column),
+            _ => return None,
+        };
+        Some(typed)
+    }
+
+    /// Returns each field of the schema paired with the `ColumnType` of its
+    /// column, as given by [`ColumnType::of_field`], in the order of the
+    /// columns.
+    ///
+    /// A field whose data type has no corresponding `ColumnType` is paired
+    /// with `None`, so that each entry stays at the index of its column.
+    #[must_use]
+    pub fn typed_schema(&self) -> Vec<(Field, Option<ColumnType>)> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|f| (f.as_ref().clone(), ColumnType::of_field(f)))
+            .collect()
+    }
+
+    /// Returns the `ColumnType` of every column, as given by
+    /// [`ColumnType::of_field`], to pass to methods such as
+    /// [`Table::statistics`].
+    ///
+    /// Returns `None` if the schema does not have one field per column, or
+    /// the type of a field is not known.
+    #[must_use]
+    pub fn column_types(&self) -> Option<Arc<Vec<ColumnType>>> {
+        if self.schema.fields().len() != self.columns.len() {
+            return None;
+        }
+        self.schema
+            .fields()
+            .iter()
+            .map(|f| ColumnType::of_field(f))
+            .collect::<Option<Vec<_>>>()
+            .map(Arc::new)
+    }
+
     /// Returns the number of columns in the table.
     #[must_use]
     pub fn num_columns(&self) -> usize {
@@ -132,16 +587,168 @@ where
         }
     }
 
+    /// Returns an iterator over the rows of the table, each of which holds
+    /// the values of the columns interpreted as `column_types`, or `None` for
+    /// a null. `Enum` values are the raw enum ids as `Element::UInt`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `column_types` does not match the columns.
+    pub fn rows(
+        &self,
+        column_types: &[ColumnType],
+    ) -> Result<impl Iterator<Item = Vec<Option<Element>>> + '_, TypeError> {
+        if column_types.len() != self.columns.len() {
+            return Err(TypeError());
+        }
+        let mut cells = self
+            .columns
+            .iter()
+            .zip(column_types)
+            .map(|(column, &column_type)| column.cells(column_type))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((0..self.num_rows()).map(move |_| {
+            cells
+                .iter_mut()
+                .map(|cells| cells.next().flatten())
+                .collect()
+        }))
+    }
+
+    /// Converts the table into an Arrow `RecordBatch`.
+    ///
+    /// If `r_enum_maps` is given, `Enum` columns are exported as dictionary
+    /// arrays keyed by the enum ids and carrying their labels, which can be
+    /// read back with [`Column::try_from_dictionary`]. Otherwise, they are
+    /// exported as the raw enum ids. Field names are taken from the schema of
+    /// the table, and an empty column has the type in which its
+    /// `column_type` is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `column_types` does not match the columns or
+    /// concatenating the arrays of a column fails.
+    pub fn to_record_batch(
+        &self,
+        column_types: &[ColumnType],
+        r_enum_maps: Option<&ReverseEnumMaps>,
+    ) -> Result<RecordBatch, ArrowError> {
+        if column_types.len() != self.columns.len() {
+            return Err(ArrowError::InvalidArgumentError(
+                "column types must match columns".to_string(),
+            ));
+        }
+        let mut fields = Vec::with_capacity(self.columns.len());
+        let mut arrays = Vec::with_capacity(self.columns.len());
+        for (index, (column, column_type)) in self.columns.iter().zip(column_types).enumerate() {
+            let array: Arc<dyn Array> = match (column_type, r_enum_maps) {
+                (ColumnType::Enum, Some(r_enum_maps)) => Arc::new(
+                    column
+                        .to_dictionary(r_enum_maps.get(&index).unwrap_or(&HashMap::new()))
+                        .map_err(|_| {
+                            ArrowError::InvalidArgumentError(format!(
+                                "column {index} is not an enum column"
+                            ))
+                        })?,
+                ),
+                _ => column
+                    .concat()?
+                    .unwrap_or_else(|| new_empty_array(&column_type.stored_data_type())),
+            };
+            let name = self
+                .schema
+                .fields()
+                .get(index)
+                .map_or("", |field| field.name().as_str());
+            fields.push(Field::new(name, array.data_type().clone(), true));
+            arrays.push(array);
+        }
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+    }
+
+    /// Converts the table into a JSON array with an object for each row,
+    /// mapping the field names of the schema to the values, or to the column
+    /// indices if the schema has fewer fields.
+    ///
+    /// Numbers are JSON numbers, except non-finite floating-point numbers,
+    /// which are `null`. Date-times are strings in ISO 8601 format, IP
+    /// addresses are strings, and binaries are strings encoded with
+    /// `binary_encoding`. `Enum` values are labeled using `r_enum_maps`, or
+    /// written as the raw enum ids if there is no map for the column. An id
+    /// missing in the map of its column is labeled `unknown_enum_label`.
+    /// Nulls are `null`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `column_types` does not match the columns.
+    pub fn to_json_rows(
+        &self,
+        column_types: &[ColumnType],
+        r_enum_maps: &ReverseEnumMaps,
+        unknown_enum_label: &str,
+        binary_encoding: BinaryEncoding,
+    ) -> Result<serde_json::Value, TypeError> {
+        let names: Vec<String> = (0..self.columns.len())
+            .map(|index| {
+                self.schema
+                    .fields()
+                    .get(index)
+                    .map_or_else(|| index.to_string(), |field| field.name().clone())
+            })
+            .collect();
+        let rows = self
+            .rows(column_types)?
+            .map(|row| {
+                let object = row
+                    .into_iter()
+                    .zip(column_types)
+                    .enumerate()
+                    .map(|(index, (value, column_type))| {
+                        let value = match (value, column_type) {
+                            (None, _) => serde_json::Value::Null,
+                            (Some(Element::UInt(v)), ColumnType::Enum) => {
+                                match r_enum_maps.get(&index) {
+                                    Some(map) => enum_label(map, v, unknown_enum_label).into(),
+                                    None => v.into(),
+                                }
+                            }
+                            (Some(value), _) => json_value(value, binary_encoding),
+                        };
+                        (names[index].clone(), value)
+                    })
+                    .collect();
+                serde_json::Value::Object(object)
+            })
+            .collect();
+        Ok(serde_json::Value::Array(rows))
+    }
+
+    /// Returns the number of null values in each column, as pairs of a column
+    /// index and its null count, in column order.
+    #[must_use]
+    pub fn null_report(&self) -> Vec<(usize, usize)> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| (index, column.null_count()))
+            .collect()
+    }
+
     /// Returns the schema of the table.
     #[must_use]
     pub fn schema(&self) -> &Arc<Schema> {
         &self.schema
     }
 
-    /// # Panics
+    /// Returns the statistics of the columns over `rows`. An enum id missing
+    /// in the reverse map of its column is labeled `_NO_MAP_`; see
+    /// [`Table::statistics_with_unknown_enum_label`] to label it otherwise.
     ///
-    /// Panics if time intervals or number of top n is not defined.
-    #[must_use]
+    /// # Errors
+    ///
+    /// Returns an error if `column_types` or `numbers_of_top_n` does not have
+    /// one entry per column, or `time_intervals` does not have one entry per
+    /// `DateTime` column.
     pub fn statistics(
         &self,
         rows: &[usize],
@@ -150,67 +757,285 @@ where
         time_intervals: &Arc<Vec<u32>>,
         numbers_of_top_n: &Arc<Vec<u32>>,
         precision: i32,
-    ) -> Vec<ColumnStatistics> {
-        self.columns
+    ) -> Result<Vec<ColumnStatistics>, TableError> {
+        self.statistics_with_unknown_enum_label(
+            rows,
+            column_types,
+            r_enum_maps,
+            time_intervals,
+            numbers_of_top_n,
+            precision,
+            UNKNOWN_ENUM_LABEL,
+        )
+    }
+
+    /// Returns the statistics of the columns, like [`Table::statistics`],
+    /// labeling an enum id missing in the reverse map of its column
+    /// `unknown_enum_label`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration does not match the columns as
+    /// described in [`Table::statistics`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn statistics_with_unknown_enum_label(
+        &self,
+        rows: &[usize],
+        column_types: &Arc<Vec<ColumnType>>,
+        r_enum_maps: &ReverseEnumMaps,
+        time_intervals: &Arc<Vec<u32>>,
+        numbers_of_top_n: &Arc<Vec<u32>>,
+        precision: i32,
+        unknown_enum_label: &str,
+    ) -> Result<Vec<ColumnStatistics>, TableError> {
+        self.check_statistics_config(column_types, time_intervals, numbers_of_top_n)?;
+        Ok((0..self.columns.len())
+            .map(|index| {
+                self.column_statistics(
+                    index,
+                    rows,
+                    column_types,
+                    r_enum_maps,
+                    time_intervals,
+                    numbers_of_top_n,
+                    precision,
+                    unknown_enum_label,
+                )
+            })
+            .collect())
+    }
+
+    /// Checks that the per-column configuration of [`Table::statistics`]
+    /// matches the columns of the table.
+    fn check_statistics_config(
+        &self,
+        column_types: &[ColumnType],
+        time_intervals: &[u32],
+        numbers_of_top_n: &[u32],
+    ) -> Result<(), TableError> {
+        let expected = self.columns.len();
+        if column_types.len() != expected {
+            return Err(TableError::NumColumnsMismatch {
+                expected,
+                found: column_types.len(),
+            });
+        }
+        if numbers_of_top_n.len() != expected {
+            return Err(TableError::NumTopNMismatch {
+                expected,
+                found: numbers_of_top_n.len(),
+            });
+        }
+        let expected = column_types
             .iter()
-            .enumerate()
-            .map(|(index, column)| {
-                let description = describe(column, rows, column_types[index]);
-                let n_largest_count = if let ColumnType::Enum = column_types[index] {
-                    n_largest_count_enum(
-                        column,
-                        rows,
-                        r_enum_maps.get(&index).unwrap_or(&HashMap::new()),
-                        *numbers_of_top_n
-                            .get(index)
-                            .expect("top N number for each column should exist."),
-                    )
-                } else if let ColumnType::DateTime = column_types[index] {
-                    let mut cn: usize = 0;
-                    for i in 0..index {
-                        if let ColumnType::DateTime = column_types[i] {
-                            cn += 1;
-                        }
-                    }
-                    n_largest_count_datetime(
-                        column,
-                        rows,
-                        *time_intervals
-                            .get(cn)
-                            .expect("time intervals should exist."),
-                        *numbers_of_top_n
-                            .get(index)
-                            .expect("top N number for each column should exist."),
-                    )
-                } else if let ColumnType::Float64 = column_types[index] {
-                    n_largest_count_float64(
-                        column,
-                        rows,
-                        *numbers_of_top_n
-                            .get(index)
-                            .expect("top N number for each column should exist."),
-                        precision,
-                    )
-                } else {
-                    n_largest_count(
-                        column,
-                        rows,
-                        column_types[index],
-                        *numbers_of_top_n
-                            .get(index)
-                            .expect("top N number for each column should exist."),
-                    )
-                };
+            .filter(|&&t| t == ColumnType::DateTime)
+            .count();
+        if time_intervals.len() != expected {
+            return Err(TableError::NumTimeIntervalsMismatch {
+                expected,
+                found: time_intervals.len(),
+            });
+        }
+        Ok(())
+    }
 
-                ColumnStatistics {
-                    description,
-                    n_largest_count,
-                }
+    /// Returns the statistics of the columns, like [`Table::statistics`],
+    /// over the rows whose timestamps in `time_column` are in
+    /// `[start, end)`. Rows with null timestamps are excluded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `time_column` does not exist or is not a
+    /// timestamp column stored as `i64`, or if the configuration does not
+    /// match the columns as described in [`Table::statistics`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn statistics_in_time_range(
+        &self,
+        time_column: usize,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        column_types: &Arc<Vec<ColumnType>>,
+        r_enum_maps: &ReverseEnumMaps,
+        time_intervals: &Arc<Vec<u32>>,
+        numbers_of_top_n: &Arc<Vec<u32>>,
+        precision: i32,
+    ) -> Result<Vec<ColumnStatistics>, TableError> {
+        let to_nanos = |t: NaiveDateTime| {
+            let t = t.and_utc();
+            t.timestamp_nanos_opt().unwrap_or(if t.timestamp() < 0 {
+                i64::MIN
+            } else {
+                i64::MAX
             })
-            .collect()
+        };
+        let (start, end) = (to_nanos(start), to_nanos(end));
+        let rows: Vec<usize> = self
+            .columns
+            .get(time_column)
+            .ok_or(TableError::ColumnIndexOutOfRange(time_column))?
+            .iter::<Int64Array>()
+            .map_err(|_| TableError::UnexpectedType(time_column))?
+            .enumerate()
+            .filter_map(|(row, t)| t.filter(|t| (start..end).contains(t)).map(|_| row))
+            .collect();
+        self.statistics(
+            &rows,
+            column_types,
+            r_enum_maps,
+            time_intervals,
+            numbers_of_top_n,
+            precision,
+        )
+    }
+
+    /// Writes the statistics of the columns, like [`Table::statistics`], to
+    /// `out` as newline-delimited JSON, one object per column with its
+    /// `column` index, field `name`, `type`, `description`, and
+    /// `n_largest_count`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration does not match the columns as
+    /// described in [`Table::statistics`], or writing to `out` fails.
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_statistics_ndjson<W: std::io::Write>(
+        &self,
+        mut out: W,
+        rows: &[usize],
+        column_types: &Arc<Vec<ColumnType>>,
+        r_enum_maps: &ReverseEnumMaps,
+        time_intervals: &Arc<Vec<u32>>,
+        numbers_of_top_n: &Arc<Vec<u32>>,
+        precision: i32,
+    ) -> Result<(), TableError> {
+        let statistics = self.statistics(
+            rows,
+            column_types,
+            r_enum_maps,
+            time_intervals,
+            numbers_of_top_n,
+            precision,
+        )?;
+        for (column, (statistics, &column_type)) in
+            statistics.iter().zip(column_types.iter()).enumerate()
+        {
+            let line = StatisticsLine {
+                column,
+                name: self.schema.fields().get(column).map(|f| f.name().as_str()),
+                column_type,
+                statistics,
+            };
+            serde_json::to_writer(&mut out, &line).map_err(std::io::Error::from)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Returns a handle to the statistics of the columns, like
+    /// [`Table::statistics`], that computes the statistics of each column only
+    /// when it is first accessed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration does not match the columns as
+    /// described in [`Table::statistics`].
+    pub fn lazy_statistics<'a>(
+        &'a self,
+        rows: &'a [usize],
+        column_types: &'a [ColumnType],
+        r_enum_maps: &'a ReverseEnumMaps,
+        time_intervals: &'a [u32],
+        numbers_of_top_n: &'a [u32],
+        precision: i32,
+    ) -> Result<LazyStatistics<'a, I>, TableError> {
+        self.check_statistics_config(column_types, time_intervals, numbers_of_top_n)?;
+        Ok(LazyStatistics {
+            table: self,
+            rows,
+            column_types,
+            r_enum_maps,
+            time_intervals,
+            numbers_of_top_n,
+            precision,
+            unknown_enum_label: UNKNOWN_ENUM_LABEL,
+            statistics: (0..self.columns.len()).map(|_| OnceCell::new()).collect(),
+        })
+    }
+
+    /// # Panics
+    ///
+    /// Panics if time intervals or number of top n is not defined.
+    #[allow(clippy::too_many_arguments)]
+    fn column_statistics(
+        &self,
+        index: usize,
+        rows: &[usize],
+        column_types: &[ColumnType],
+        r_enum_maps: &ReverseEnumMaps,
+        time_intervals: &[u32],
+        numbers_of_top_n: &[u32],
+        precision: i32,
+        unknown_enum_label: &str,
+    ) -> ColumnStatistics {
+        let column = &self.columns[index].widened();
+        let description = describe(column, rows, column_types[index]);
+        let n_largest_count = if let ColumnType::Enum = column_types[index] {
+            n_largest_count_enum(
+                column,
+                rows,
+                r_enum_maps.get(&index).unwrap_or(&HashMap::new()),
+                *numbers_of_top_n
+                    .get(index)
+                    .expect("top N number for each column should exist."),
+                unknown_enum_label,
+            )
+        } else if let ColumnType::DateTime = column_types[index] {
+            let cn = column_types[..index]
+                .iter()
+                .filter(|&&t| t == ColumnType::DateTime)
+                .count();
+            n_largest_count_datetime(
+                column,
+                rows,
+                *time_intervals
+                    .get(cn)
+                    .expect("time intervals should exist."),
+                *numbers_of_top_n
+                    .get(index)
+                    .expect("top N number for each column should exist."),
+            )
+        } else if let ColumnType::Float64 = column_types[index] {
+            n_largest_count_float64(
+                column,
+                rows,
+                *numbers_of_top_n
+                    .get(index)
+                    .expect("top N number for each column should exist."),
+                precision,
+            )
+        } else {
+            n_largest_count(
+                column,
+                rows,
+                column_types[index],
+                *numbers_of_top_n
+                    .get(index)
+                    .expect("top N number for each column should exist."),
+            )
+        };
+
+        ColumnStatistics {
+            description,
+            n_largest_count,
+        }
     }
 
-    // count means including only positive values. Implement other functions like sum_group_by, mean_group_by, etc. later.
+    /// Counts the rows or the values of `count_columns` by groups, where
+    /// `by_interval` is in seconds. An interval longer than a day is treated
+    /// as a day.
+    ///
+    /// See [`Table::count_group_by_interval`].
+    ///
     /// # Panics
     ///
     /// Panics if columns are not defined.
@@ -223,9 +1048,33 @@ where
         by_interval: Option<u32>,
         count_columns: &Arc<Vec<usize>>,
     ) -> Vec<GroupCount> {
-        let column_type = if let Some(column_type) = column_types.get(by_column) {
-            *column_type
-        } else {
+        self.count_group_by_interval(
+            rows,
+            column_types,
+            by_column,
+            by_interval.map(Interval::clamped_secs),
+            count_columns,
+        )
+    }
+
+    /// Counts the rows or the values of `count_columns` by groups of
+    /// `by_interval` in `by_column`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if columns are not defined.
+    #[must_use]
+    pub fn count_group_by_interval(
+        &self,
+        rows: &[usize],
+        column_types: &Arc<Vec<ColumnType>>,
+        by_column: usize,
+        by_interval: Option<Interval>,
+        count_columns: &Arc<Vec<usize>>,
+    ) -> Vec<GroupCount> {
+        let column_type = if let Some(column_type) = column_types.get(by_column) {
+            *column_type
+        } else {
             return Vec::new();
         };
 
@@ -261,6 +1110,7 @@ where
                     }
                 } else if let ColumnType::Int64 = column_types[count_index] {
                     let counts = column
+                        .widened()
                         .primitive_iter::<Int64Type>(rows)
                         .expect("expecting Int64Type only")
                         .map(|v| v.to_usize().unwrap_or(0)) // if count is negative, then 0
@@ -300,211 +1150,2033 @@ where
             .collect()
     }
 
+    /// Finds the most frequent value of `value_column` among the rows in
+    /// each group of `by_interval` in `by_column`, as in
+    /// [`Table::count_group_by_interval`]. Returns the start of each group with its
+    /// most frequent value, in chronological order.
+    ///
+    /// Nulls are skipped, and a group without any other value is omitted. A
+    /// tie goes to the smallest value. `Enum` values are the raw enum ids as
+    /// `Element::UInt`. Nothing is returned unless `by_column` is a
+    /// `DateTime` column and `by_interval` is given.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value_column` does not exist or is not of the
+    /// type given in `column_types`.
+    pub fn mode_group_by(
+        &self,
+        rows: &[usize],
+        column_types: &Arc<Vec<ColumnType>>,
+        by_column: usize,
+        by_interval: Option<Interval>,
+        value_column: usize,
+    ) -> Result<Vec<(GroupElement, Element)>, TableError> {
+        let (Some(column), Some(&value_type)) = (
+            self.columns.get(value_column),
+            column_types.get(value_column),
+        ) else {
+            return Err(TableError::ColumnIndexOutOfRange(value_column));
+        };
+        let (Some(ColumnType::DateTime), Some(by_column), Some(by_interval)) = (
+            column_types.get(by_column),
+            self.columns.get(by_column),
+            by_interval,
+        ) else {
+            return Ok(Vec::new());
+        };
+
+        let times = convert_time_intervals(by_column, rows, by_interval);
+        let mut groups: BTreeMap<NaiveDateTime, Vec<Element>> = BTreeMap::new();
+        for (&row, time) in rows.iter().zip(times) {
+            let value = column
+                .element_at(row, value_type)
+                .map_err(|_| TableError::UnexpectedType(value_column))?;
+            if let Some(value) = value {
+                groups.entry(time).or_default().push(value);
+            }
+        }
+
+        Ok(groups
+            .into_iter()
+            .filter_map(|(time, mut values)| {
+                values.sort_unstable_by(cmp_elements);
+                // The first index and the length of the longest run
+                let mut mode: Option<(usize, usize)> = None;
+                let mut start = 0;
+                while start < values.len() {
+                    let end = values[start..]
+                        .iter()
+                        .position(|v| cmp_elements(&values[start], v).is_ne())
+                        .map_or(values.len(), |len| start + len);
+                    if mode.map_or(true, |(_, count)| end - start > count) {
+                        mode = Some((start, end - start));
+                    }
+                    start = end;
+                }
+                mode.map(|(index, _)| (GroupElement::DateTime(time), values.swap_remove(index)))
+            })
+            .collect())
+    }
+
+    /// Counts the rows or the values of `count_columns` by groups, like
+    /// [`Table::count_group_by`], but rejects invalid column indices instead
+    /// of skipping them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `by_column` or any of `count_columns` is out of
+    /// range for the table or `column_types`.
+    pub fn try_count_group_by(
+        &self,
+        rows: &[usize],
+        column_types: &Arc<Vec<ColumnType>>,
+        by_column: usize,
+        by_interval: Option<u32>,
+        count_columns: &Arc<Vec<usize>>,
+    ) -> Result<Vec<GroupCount>, TableError> {
+        let num_columns = self.columns.len().min(column_types.len());
+        if let Some(&index) = std::iter::once(&by_column)
+            .chain(count_columns.iter())
+            .find(|&&index| index >= num_columns)
+        {
+            return Err(TableError::ColumnIndexOutOfRange(index));
+        }
+        Ok(self.count_group_by(rows, column_types, by_column, by_interval, count_columns))
+    }
+
     #[must_use]
     pub fn event_index(&self, eventid: &I) -> Option<&usize> {
         self.event_ids.get(eventid)
     }
-}
 
-/// A single column in a table.
-#[derive(Clone, Debug, Default)]
-pub struct Column {
-    arrays: Vec<Arc<dyn Array>>,
-    cumlen: Vec<usize>,
-    len: usize,
-}
+    /// Returns the row index of each event in `events`, or `None` for an
+    /// unknown event, in the same order.
+    #[must_use]
+    pub fn event_indices(&self, events: &[I]) -> Vec<Option<usize>> {
+        events
+            .iter()
+            .map(|event| self.event_ids.get(event).copied())
+            .collect()
+    }
 
-impl Column {
-    /// Converts a slice into a `Column`.
+    /// Returns the map from each event id to its row index.
+    #[must_use]
+    pub fn event_ids(&self) -> &HashMap<I, usize> {
+        &self.event_ids
+    }
+
+    /// Replaces the event ids with the values of column `event_id_column` of
+    /// type `T`, mapping each to its current row index. This brings the event
+    /// ids up to date after the rows are reordered. Rows with a null event id
+    /// are skipped, and the last row wins if an event id appears more than
+    /// once.
     ///
     /// # Errors
     ///
-    /// Returns an error if array operation failed.
-    pub fn try_from_slice<T>(slice: &[T::Native]) -> arrow::error::Result<Self>
+    /// Returns an error if there is no column at `event_id_column`, or it is
+    /// not of type `T`. The event ids are unchanged in that case.
+    pub fn rebuild_event_ids<T>(&mut self, event_id_column: usize) -> Result<(), TableError>
     where
         T: ArrowPrimitiveType,
+        I: From<T::Native>,
     {
-        let mut builder = PrimitiveBuilder::<T>::new();
-        for s in slice {
-            builder.append_value(*s);
-        }
-        let array: Arc<dyn Array> = Arc::new(builder.finish());
-        Ok(array.into())
-    }
-
-    fn len(&self) -> usize {
-        self.len
+        let column = self
+            .columns
+            .get(event_id_column)
+            .ok_or(TableError::ColumnIndexOutOfRange(event_id_column))?;
+        let values = column
+            .iter::<PrimitiveArray<T>>()
+            .map_err(|_| TableError::UnexpectedType(event_id_column))?;
+        self.event_ids = values
+            .enumerate()
+            .filter_map(|(row, id)| id.map(|id| (I::from(id), row)))
+            .collect();
+        Ok(())
     }
 
-    /// Return the value specified by the index as type T
+    /// Returns a new column taking the value of column `primary` in each row,
+    /// or that of column `backup` if the former is null.
     ///
     /// # Errors
     ///
-    /// Returns an error if it's fail to convert the value to the specified type
-    pub fn primitive_try_get<T>(&self, index: usize) -> Result<Option<T::Native>, TypeError>
-    where
-        T: ArrowPrimitiveType,
-    {
-        if index >= self.len() {
-            return Ok(None);
+    /// Returns an error if either column does not exist, or the two columns
+    /// have different types.
+    pub fn coalesce(&self, primary: usize, backup: usize) -> Result<Column, TableError> {
+        let primary_column = self
+            .columns
+            .get(primary)
+            .ok_or(TableError::ColumnIndexOutOfRange(primary))?;
+        let backup_column = self
+            .columns
+            .get(backup)
+            .ok_or(TableError::ColumnIndexOutOfRange(backup))?;
+        match (primary_column.data_type(), backup_column.data_type()) {
+            (Some(p), Some(b)) if p != b => {
+                return Err(TableError::TypeMismatch(primary, backup));
+            }
+            _ => {}
         }
-        let (array_index, inner_index) = match self.cumlen.binary_search(&index) {
-            Ok(i) => (i, 0),
-            Err(i) => (i - 1, index - self.cumlen[i - 1]),
-        };
-        let Some(typed_arr) = self.arrays[array_index]
-            .as_any()
-            .downcast_ref::<PrimitiveArray<T>>()
+
+        let (Some(primary_array), Some(backup_array)) =
+            (primary_column.concat()?, backup_column.concat()?)
         else {
-            return Err(TypeError());
+            return Ok(Column::default());
         };
-        Ok(Some(typed_arr.value(inner_index)))
+        let mask = arrow::compute::is_not_null(primary_array.as_ref())?;
+        let array = arrow::compute::kernels::zip::zip(
+            &mask,
+            &primary_array.as_ref(),
+            &backup_array.as_ref(),
+        )?;
+        Ok(array.into())
     }
 
-    /// Return the value specified by the index as byte array
+    /// Replaces the values of column `index` of type `T` with `f` applied to
+    /// them, and its field in the schema with `field`. Null values stay null.
+    /// A `Timestamp` field is taken as the `Int64` type in which its values
+    /// are stored.
     ///
     /// # Errors
     ///
-    /// Returns an error if it's fail to convert the value to byte array
-    pub fn binary_try_get(&self, index: usize) -> Result<Option<&[u8]>, TypeError> {
-        if index >= self.len() {
-            return Ok(None);
-        }
-        let (array_index, inner_index) = match self.cumlen.binary_search(&index) {
-            Ok(i) => (i, 0),
-            Err(i) => (i - 1, index - self.cumlen[i - 1]),
+    /// Returns an error if there is no column or field at `index`, the field
+    /// or the column is not of type `T`, even if the column is empty, or
+    /// `field` does not have the data type of `U`. The table is unchanged in
+    /// that case.
+    pub fn map_column<T, U, F>(
+        &mut self,
+        index: usize,
+        field: Field,
+        f: F,
+    ) -> Result<(), TableError>
+    where
+        T: ArrowPrimitiveType,
+        U: ArrowPrimitiveType,
+        F: Fn(T::Native) -> U::Native,
+    {
+        let column = self
+            .columns
+            .get(index)
+            .ok_or(TableError::ColumnIndexOutOfRange(index))?;
+        let Some(current) = self.schema.fields().get(index) else {
+            return Err(TableError::ColumnIndexOutOfRange(index));
         };
-        let Some(typed_arr) = self.arrays[array_index]
-            .as_any()
-            .downcast_ref::<BinaryArray>()
-        else {
-            return Err(TypeError());
+        if !is_stored_as(current.data_type(), &T::DATA_TYPE)
+            || column
+                .data_type()
+                .is_some_and(|data_type| data_type != &T::DATA_TYPE)
+            || !is_stored_as(field.data_type(), &U::DATA_TYPE)
+        {
+            return Err(TableError::UnexpectedType(index));
+        }
+        let arrays = column
+            .arrays
+            .iter()
+            .map(|arr| {
+                let arr = arr
+                    .as_any()
+                    .downcast_ref::<PrimitiveArray<T>>()
+                    .ok_or(TableError::UnexpectedType(index))?;
+                Ok(Arc::new(arr.unary::<_, U>(&f)) as Arc<dyn Array>)
+            })
+            .collect::<Result<_, TableError>>()?;
+
+        let mut fields: Vec<Field> = self
+            .schema
+            .fields()
+            .iter()
+            .map(|f| f.as_ref().clone())
+            .collect();
+        fields[index] = field;
+        self.schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            self.schema.metadata().clone(),
+        ));
+        self.columns[index] = Column {
+            arrays,
+            cumlen: column.cumlen.clone(),
+            len: column.len,
         };
-        Ok(Some(typed_arr.value(inner_index)))
+        Ok(())
     }
 
-    /// Return the value specified by the index as string
+    /// Returns a random sample of the row indices in ascending order, each
+    /// row chosen independently with probability `fraction`. The same `seed`
+    /// and `fraction` always choose the same rows of a table.
     ///
     /// # Errors
     ///
-    /// Returns an error if it's fail to convert the value to string
-    pub fn string_try_get(&self, index: usize) -> Result<Option<&str>, TypeError> {
-        if index >= self.len() {
-            return Ok(None);
+    /// Returns an error if `fraction` is not between 0 and 1, inclusive.
+    pub fn sample_rows(&self, fraction: f64, seed: u64) -> Result<Vec<usize>, TableError> {
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(TableError::InvalidFraction(fraction));
         }
-        let (array_index, inner_index) = match self.cumlen.binary_search(&index) {
-            Ok(i) => (i, 0),
-            Err(i) => (i - 1, index - self.cumlen[i - 1]),
-        };
-        let Some(typed_arr) = self.arrays[array_index]
-            .as_any()
-            .downcast_ref::<StringArray>()
-        else {
-            return Err(TypeError());
-        };
-        Ok(Some(typed_arr.value(inner_index)))
+        let mut state = seed;
+        Ok((0..self.num_rows())
+            .filter(|_| {
+                // SplitMix64
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^= z >> 31;
+                #[allow(clippy::cast_precision_loss)] // 53 bits fit in f64 exactly
+                let uniform = (z >> 11) as f64 / (1_u64 << 53) as f64;
+                uniform < fraction
+            })
+            .collect())
     }
 
-    fn append(&mut self, other: &mut Self) {
-        // TODO: make sure the types match
-        self.arrays.append(&mut other.arrays);
-        let len = self.len;
-        self.cumlen
-            .extend(other.cumlen.iter().skip(1).map(|v| v + len));
-        self.len += other.len;
-        other.len = 0;
+    /// Returns the indices of the columns whose non-null values are all
+    /// equal, as determined by [`Column::is_constant`].
+    #[must_use]
+    pub fn constant_columns(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| column.is_constant(None))
+            .map(|(index, _)| index)
+            .collect()
     }
 
-    /// Creates an iterator iterating over all the cells in this `Column`.
+    /// Returns the indices of the rows for which `filter` holds, in ascending
+    /// order.
     ///
     /// # Errors
     ///
-    /// Returns an error if the type parameter does not match with the type of
-    /// this `Column`.
-    #[allow(clippy::iter_not_returning_iterator)]
-    // TODO: resolve this clippy warning
-    pub fn iter<'a, T>(&'a self) -> Result<Flatten<vec::IntoIter<&'a T>>, TypeError>
-    where
-        T: Array + 'static,
-        &'a T: IntoIterator,
-    {
-        let mut arrays: Vec<&T> = Vec::with_capacity(self.arrays.len());
-        for arr in &self.arrays {
-            let Some(typed_arr) = arr.as_any().downcast_ref::<T>() else {
-                return Err(TypeError());
+    /// Returns an error if `filter` refers to a column that does not exist,
+    /// or applies a predicate to a column of a different type.
+    pub fn rows_matching(&self, filter: &RowFilter) -> Result<Vec<usize>, TableError> {
+        let mut rows = Vec::new();
+        for row in 0..self.num_rows() {
+            if filter.matches(&self.columns, row)? {
+                rows.push(row);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// Returns the Pearson correlation coefficient between columns `col_a`
+    /// and `col_b` over the rows designated by `rows` where neither value is
+    /// null. Both columns must be of a primitive integer or floating-point
+    /// type.
+    ///
+    /// Returns `None` if either column does not exist or is not numeric, there
+    /// are fewer than two such rows, or either column has no variance.
+    #[must_use]
+    pub fn correlation(&self, col_a: usize, col_b: usize, rows: &[usize]) -> Option<f64> {
+        let (column_a, column_b) = (self.columns.get(col_a)?, self.columns.get(col_b)?);
+        // Welford's online algorithm, extended to the co-moment.
+        let (mut count, mut mean_a, mut mean_b) = (0.0, 0.0, 0.0);
+        let (mut m2_a, mut m2_b, mut co_moment) = (0.0, 0.0, 0.0);
+        for &row in rows {
+            let (Some(x), Some(y)) = (column_a.f64_at(row).ok()?, column_b.f64_at(row).ok()?)
+            else {
+                continue;
             };
-            arrays.push(typed_arr);
+            count += 1.0;
+            let dx = x - mean_a;
+            let dy = y - mean_b;
+            mean_a += dx / count;
+            mean_b += dy / count;
+            m2_a += dx * (x - mean_a);
+            m2_b += dy * (y - mean_b);
+            co_moment += dx * (y - mean_b);
         }
-        Ok(arrays.into_iter().flatten())
+        if count < 2.0 || m2_a <= 0.0 || m2_b <= 0.0 {
+            return None;
+        }
+        Some((co_moment / (m2_a * m2_b).sqrt()).clamp(-1.0, 1.0))
     }
 
-    /// Creates an iterator iterating over a subset of the cells in this
-    /// `Column` of primitive type, designated by `selected`.
+    /// Returns the indices of the `k` rows with the largest values, or the
+    /// smallest if `largest` is `false`, in column `column` of type `T`,
+    /// ordered from the most extreme. Nulls are skipped, and equal values are
+    /// ordered by row index. It takes O(n log k) time for n rows.
     ///
     /// # Errors
     ///
-    /// Returns an error if the type parameter does not match with the type of
-    /// this `Column`.
-    pub fn primitive_iter<'a, 'b, T>(
-        &'a self,
-        selected: &'b [usize],
-    ) -> Result<PrimitiveIter<'a, 'b, T>, TypeError>
+    /// Returns an error if there is no column at `column`, or it is not of
+    /// type `T`.
+    pub fn top_k_rows<T>(
+        &self,
+        column: usize,
+        k: usize,
+        largest: bool,
+    ) -> Result<Vec<usize>, TableError>
     where
         T: ArrowPrimitiveType,
     {
-        Ok(PrimitiveIter::new(self, selected.iter()))
+        let values = self
+            .columns
+            .get(column)
+            .ok_or(TableError::ColumnIndexOutOfRange(column))?;
+        Ok(values
+            .n_extreme_values::<T>(0..values.len(), k, largest)
+            .map_err(|_| TableError::UnexpectedType(column))?
+            .into_iter()
+            .map(|(row, _)| row)
+            .collect())
     }
 
-    /// Creates an iterator iterating over a subset of the cells in this
-    /// `Column` of binaries, designated by `selected`.
+    /// Returns a table whose columns are those of this table at the source
+    /// indices of `mapping`, in order, each named after its new name. A
+    /// column may appear more than once. The fields keep their data types,
+    /// nullability, and metadata; a column without a field in the schema
+    /// gets a nullable field of its data type. The columns share the arrays
+    /// of this table, and the event ids are kept.
     ///
     /// # Errors
     ///
-    /// Returns an error if the type parameter does not match with the type of
-    /// this `Column`.
-    pub fn binary_iter<'a, 'b>(
-        &'a self,
-        selected: &'b [usize],
-    ) -> Result<BinaryIter<'a, 'b>, TypeError> {
-        Ok(BinaryIter::new(self, selected.iter()))
+    /// Returns an error if a source index is out of range.
+    pub fn remap(&self, mapping: &[(usize, &str)]) -> Result<Self, TableError>
+    where
+        I: Clone,
+    {
+        let mut fields = Vec::with_capacity(mapping.len());
+        let mut columns = Vec::with_capacity(mapping.len());
+        for &(source, name) in mapping {
+            let column = self
+                .columns
+                .get(source)
+                .ok_or(TableError::ColumnIndexOutOfRange(source))?;
+            let field = match self.schema.fields().get(source) {
+                Some(field) => field.as_ref().clone().with_name(name),
+                None => Field::new(
+                    name,
+                    column.data_type().cloned().unwrap_or(DataType::Null),
+                    true,
+                ),
+            };
+            fields.push(field);
+            columns.push(column.clone());
+        }
+        Ok(Self {
+            schema: Arc::new(Schema::new(fields)),
+            columns,
+            event_ids: self.event_ids.clone(),
+            max_arrays_per_column: self.max_arrays_per_column,
+        })
     }
 
-    /// Creates an iterator iterating over a subset of the cells in this
-    /// `Column` of strings, designated by `selected`.
+    /// Returns a table whose columns are those of this table at `indices`,
+    /// in order, keeping their names. This is [`Table::remap`] without
+    /// renaming; a column without a field in the schema gets an empty name.
     ///
     /// # Errors
     ///
-    /// Returns an error if the type parameter does not match with the type of
-    /// this `Column`.
-    pub fn string_iter<'a, 'b>(
-        &'a self,
-        selected: &'b [usize],
-    ) -> Result<StringIter<'a, 'b>, TypeError> {
-        Ok(StringIter::new(self, selected.iter()))
+    /// Returns an error if an index is out of range.
+    pub fn project(&self, indices: &[usize]) -> Result<Self, TableError>
+    where
+        I: Clone,
+    {
+        let mapping: Vec<_> = indices
+            .iter()
+            .map(|&i| {
+                let name = self
+                    .schema
+                    .fields()
+                    .get(i)
+                    .map_or("", |f| f.name().as_str());
+                (i, name)
+            })
+            .collect();
+        self.remap(&mapping)
     }
-}
 
-impl PartialEq for Column {
+    /// Splits the table into `n` tables of contiguous rows, in order.
+    ///
+    /// The numbers of rows in the partitions differ by at most one, with the
+    /// earlier partitions taking the extra rows. Each partition keeps the
+    /// event ids of its rows, indexed from the start of the partition. The
+    /// columns share the arrays of this table.
+    #[must_use]
+    pub fn partition(&self, n: usize) -> Vec<Self>
+    where
+        I: Clone,
+    {
+        if n == 0 {
+            return Vec::new();
+        }
+        let num_rows = self.num_rows();
+        let (size, remainder) = (num_rows / n, num_rows % n);
+        let mut start = 0;
+        (0..n)
+            .map(|i| {
+                let len = if i < remainder { size + 1 } else { size };
+                let partition = self.slice(start, len);
+                start += len;
+                partition
+            })
+            .collect()
+    }
+
+    /// Returns the `len` rows starting at `offset`, or fewer if the table
+    /// ends earlier. The slice keeps the event ids of its rows, indexed from
+    /// `offset`, and its columns share the arrays of this table.
     #[must_use]
+    pub fn slice(&self, offset: usize, len: usize) -> Self
+    where
+        I: Clone,
+    {
+        let end = offset.saturating_add(len);
+        let columns = self.columns.iter().map(|c| c.slice(offset, len)).collect();
+        let event_ids = self
+            .event_ids
+            .iter()
+            .filter(|(_, &row)| offset <= row && row < end)
+            .map(|(id, &row)| (id.clone(), row - offset))
+            .collect();
+        Self {
+            schema: self.schema.clone(),
+            columns,
+            event_ids,
+            max_arrays_per_column: self.max_arrays_per_column,
+        }
+    }
+}
+
+/// Compares two values of the same column, ordering floating-point numbers
+/// by the IEEE 754 total order.
+fn cmp_elements(a: &Element, b: &Element) -> std::cmp::Ordering {
+    match (a, b) {
+        (Element::Float(a), Element::Float(b)) => a.total_cmp(b),
+        _ => a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal),
+    }
+}
+
+/// Converts a value into JSON for [`Table::to_json_rows`].
+fn json_value(value: Element, binary_encoding: BinaryEncoding) -> serde_json::Value {
+    match value {
+        Element::Int(v) => v.into(),
+        Element::UInt(v) => v.into(),
+        Element::Float(v) => v.into(),
+        Element::Enum(v) | Element::Text(v) => v.into(),
+        Element::FloatRange(v) => {
+            serde_json::json!({ "smallest": v.smallest, "largest": v.largest })
+        }
+        Element::Binary(v) => binary_encoding.encode(&v).into(),
+        Element::IpAddr(v) => v.to_string().into(),
+        Element::DateTime(v) => v.format("%Y-%m-%dT%H:%M:%S%.f").to_string().into(),
+    }
+}
+
+/// A value ranked for [`Column::n_largest_values`] and
+/// [`Column::n_smallest_values`]. A value ranked higher compares less, and
+/// equal values are ranked by row index.
+struct Ranked<T> {
+    row: usize,
+    value: T,
+    largest: bool,
+}
+
+impl<T: ArrowNativeTypeOp> Ord for Ranked<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        let order = self.value.compare(other.value);
+        if self.largest { order.reverse() } else { order }.then(self.row.cmp(&other.row))
+    }
+}
+
+impl<T: ArrowNativeTypeOp> PartialOrd for Ranked<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ArrowNativeTypeOp> PartialEq for Ranked<T> {
     fn eq(&self, other: &Self) -> bool {
-        let data_type = match (self.arrays.first(), other.arrays.first()) {
-            (Some(x_arr), Some(y_arr)) => {
-                if x_arr.to_data().data_type() == y_arr.to_data().data_type() {
-                    x_arr.to_data().data_type().clone()
-                } else {
-                    return false;
-                }
-            }
-            (Some(_), None) | (None, Some(_)) => return false,
-            (None, None) => return true,
-        };
-        if self.len() != other.len() {
-            return false;
+        self.cmp(other).is_eq()
+    }
+}
+
+impl<T: ArrowNativeTypeOp> Eq for Ranked<T> {}
+
+/// A comparison operator for [`Column::compare_scalar_primitive`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl CompareOp {
+    /// Returns `true` if a value that compares to another as `ordering`
+    /// satisfies this operator.
+    fn holds(self, ordering: std::cmp::Ordering) -> bool {
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::Ne => ordering.is_ne(),
+            Self::Lt => ordering.is_lt(),
+            Self::LtEq => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::GtEq => ordering.is_ge(),
         }
+    }
+}
 
-        match data_type {
-            DataType::Int8 => self
-                .iter::<Int8Array>()
+/// A condition on the value of a column in a row, for [`RowFilter`]. A null
+/// value never satisfies a condition.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Predicate {
+    /// Compares the value of an `Int64` column with a constant.
+    Int64(CompareOp, i64),
+    /// Compares the value of a `UInt64` column with a constant.
+    UInt64(CompareOp, u64),
+    /// Compares the value of a `Float64` column with a constant. A NaN on
+    /// either side never satisfies the condition.
+    Float64(CompareOp, f64),
+    /// Checks if the value of a `UInt32` column of IPv4 addresses belongs to
+    /// the subnet with the given network address and prefix length.
+    InSubnet(Ipv4Addr, u8),
+    /// Checks if the value of a `Utf8` column equals a string.
+    StringEq(String),
+}
+
+/// A boolean expression over the columns of a row, for
+/// [`Table::rows_matching`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum RowFilter {
+    /// The value of the column at the index satisfies the predicate.
+    Column(usize, Predicate),
+    /// All the expressions hold. An empty list always holds.
+    And(Vec<RowFilter>),
+    /// Any of the expressions holds. An empty list never holds.
+    Or(Vec<RowFilter>),
+    /// The expression does not hold.
+    Not(Box<RowFilter>),
+}
+
+impl RowFilter {
+    /// Evaluates this expression for `row` of `columns`.
+    fn matches(&self, columns: &[Column], row: usize) -> Result<bool, TableError> {
+        match self {
+            Self::Column(index, predicate) => {
+                let column = columns
+                    .get(*index)
+                    .ok_or(TableError::ColumnIndexOutOfRange(*index))?;
+                predicate
+                    .matches(column, row)
+                    .map_err(|_| TableError::UnexpectedType(*index))
+            }
+            Self::And(filters) => {
+                for filter in filters {
+                    if !filter.matches(columns, row)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Self::Or(filters) => {
+                for filter in filters {
+                    if filter.matches(columns, row)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Self::Not(filter) => Ok(!filter.matches(columns, row)?),
+        }
+    }
+}
+
+impl Predicate {
+    /// Returns `true` if the value at `row` of `column` satisfies this
+    /// predicate.
+    fn matches(&self, column: &Column, row: usize) -> Result<bool, TypeError> {
+        let matched = match self {
+            Self::Int64(op, value) => column
+                .primitive_try_get::<Int64Type>(row)?
+                .is_some_and(|v| op.holds(v.cmp(value))),
+            Self::UInt64(op, value) => column
+                .primitive_try_get::<UInt64Type>(row)?
+                .is_some_and(|v| op.holds(v.cmp(value))),
+            Self::Float64(op, value) => column
+                .primitive_try_get::<Float64Type>(row)?
+                .and_then(|v| v.partial_cmp(value))
+                .is_some_and(|ordering| op.holds(ordering)),
+            Self::InSubnet(network, prefix_len) => !column
+                .rows_in_subnet(&[row], *network, *prefix_len)?
+                .is_empty(),
+            Self::StringEq(value) => {
+                let Some((arr, inner_index)) = column.locate(row) else {
+                    return Ok(false);
+                };
+                let typed_arr = arr
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .ok_or(TypeError())?;
+                typed_arr.is_valid(inner_index) && typed_arr.value(inner_index) == value
+            }
+        };
+        Ok(matched)
+    }
+}
+
+/// A column of a `Table` tagged with its type in the schema.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TypedColumn<'a> {
+    Int64(&'a Column),
+    Float64(&'a Column),
+    /// A timestamp column stored as `i64`.
+    DateTime(&'a Column),
+    UInt32(&'a Column),
+    UInt64(&'a Column),
+    Utf8(&'a Column),
+    Binary(&'a Column),
+}
+
+/// The statistics of the columns of a `Table`, each computed when it is
+/// first accessed and cached afterwards.
+pub struct LazyStatistics<'a, I> {
+    table: &'a Table<I>,
+    rows: &'a [usize],
+    column_types: &'a [ColumnType],
+    r_enum_maps: &'a ReverseEnumMaps,
+    time_intervals: &'a [u32],
+    numbers_of_top_n: &'a [u32],
+    precision: i32,
+    unknown_enum_label: &'a str,
+    statistics: Vec<OnceCell<ColumnStatistics>>,
+}
+
+impl<'a, I> LazyStatistics<'a, I>
+where
+    I: std::hash::Hash + PartialEq + Eq,
+{
+    /// Sets the label of the `Enum` values missing in their reverse maps,
+    /// which is `_NO_MAP_` by default. It applies only to the statistics not
+    /// computed yet.
+    #[must_use]
+    pub fn with_unknown_enum_label(mut self, label: &'a str) -> Self {
+        self.unknown_enum_label = label;
+        self
+    }
+
+    /// Returns the statistics of the column at `index`, computing them if
+    /// they have not been accessed before, or `None` if `index` is out of
+    /// range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if time intervals or number of top n is not defined for the
+    /// column.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&ColumnStatistics> {
+        let cell = self.statistics.get(index)?;
+        Some(cell.get_or_init(|| {
+            self.table.column_statistics(
+                index,
+                self.rows,
+                self.column_types,
+                self.r_enum_maps,
+                self.time_intervals,
+                self.numbers_of_top_n,
+                self.precision,
+                self.unknown_enum_label,
+            )
+        }))
+    }
+
+    /// Returns `true` if the statistics of the column at `index` have been
+    /// computed.
+    #[must_use]
+    pub fn is_computed(&self, index: usize) -> bool {
+        self.statistics
+            .get(index)
+            .is_some_and(|cell| cell.get().is_some())
+    }
+
+    /// Returns the number of columns.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.statistics.len()
+    }
+
+    /// Returns `true` if there are no columns.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.statistics.is_empty()
+    }
+}
+
+/// A column of boolean flags, stored either densely as a bitmap or sparsely
+/// as the indices of the set flags.
+///
+/// The dense form takes one bit per row, regardless of the values. The sparse
+/// form takes `usize::BITS` bits per set flag, so it is smaller only when
+/// fewer than one in `usize::BITS` flags, e.g., one in 64, are set. Looking
+/// up a flag takes constant time in the dense form, and logarithmic time in
+/// the number of set flags in the sparse form.
+///
+/// Two `FlagColumn`s are equal if they have the same flags, whichever form
+/// they are in.
+#[derive(Clone, Debug)]
+pub enum FlagColumn {
+    Dense(BooleanBuffer),
+    Sparse {
+        /// The number of rows.
+        len: usize,
+        /// The indices of the set flags, in ascending order.
+        set: Vec<usize>,
+    },
+}
+
+impl FlagColumn {
+    /// Creates a `FlagColumn` in whichever form takes less memory for
+    /// `flags`.
+    #[must_use]
+    pub fn new(flags: &[bool]) -> Self {
+        let num_set = flags.iter().filter(|&&flag| flag).count();
+        if num_set.saturating_mul(usize::BITS as usize) < flags.len() {
+            Self::sparse(flags)
+        } else {
+            Self::dense(flags)
+        }
+    }
+
+    /// Creates a `FlagColumn` in the dense form.
+    #[must_use]
+    pub fn dense(flags: &[bool]) -> Self {
+        Self::Dense(flags.iter().copied().collect())
+    }
+
+    /// Creates a `FlagColumn` in the sparse form.
+    #[must_use]
+    pub fn sparse(flags: &[bool]) -> Self {
+        Self::Sparse {
+            len: flags.len(),
+            set: flags
+                .iter()
+                .enumerate()
+                .filter_map(|(i, &flag)| flag.then_some(i))
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if this column is in the sparse form.
+    #[must_use]
+    pub fn is_sparse(&self) -> bool {
+        matches!(self, Self::Sparse { .. })
+    }
+
+    /// Returns the number of rows.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Dense(bits) => bits.len(),
+            Self::Sparse { len, .. } => *len,
+        }
+    }
+
+    /// Returns `true` if there is no row.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the flag at `index` is set. An index out of range
+    /// is not set.
+    #[must_use]
+    pub fn is_set(&self, index: usize) -> bool {
+        match self {
+            Self::Dense(bits) => index < bits.len() && bits.value(index),
+            Self::Sparse { set, .. } => set.binary_search(&index).is_ok(),
+        }
+    }
+
+    /// Returns the number of set flags.
+    #[must_use]
+    pub fn count_set(&self) -> usize {
+        match self {
+            Self::Dense(bits) => bits.count_set_bits(),
+            Self::Sparse { set, .. } => set.len(),
+        }
+    }
+
+    /// Returns an iterator over the indices of the set flags, in ascending
+    /// order.
+    #[must_use]
+    pub fn set_indices(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        match self {
+            Self::Dense(bits) => Box::new(bits.set_indices()),
+            Self::Sparse { set, .. } => Box::new(set.iter().copied()),
+        }
+    }
+
+    /// Returns an iterator over all the flags, in order.
+    #[must_use]
+    pub fn iter(&self) -> Box<dyn Iterator<Item = bool> + '_> {
+        match self {
+            Self::Dense(bits) => Box::new(bits.iter()),
+            Self::Sparse { len, set } => {
+                let mut set = set.iter().peekable();
+                Box::new((0..*len).map(move |i| set.next_if_eq(&&i).is_some()))
+            }
+        }
+    }
+}
+
+impl PartialEq for FlagColumn {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.set_indices().eq(other.set_indices())
+    }
+}
+
+impl Eq for FlagColumn {}
+
+impl<'a> IntoIterator for &'a FlagColumn {
+    type Item = bool;
+    type IntoIter = Box<dyn Iterator<Item = bool> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A single column in a table.
+#[derive(Clone, Debug, Default)]
+pub struct Column {
+    arrays: Vec<Arc<dyn Array>>,
+    cumlen: Vec<usize>,
+    len: usize,
+}
+
+impl Column {
+    /// Converts a slice into a `Column`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if array operation failed.
+    pub fn try_from_slice<T>(slice: &[T::Native]) -> arrow::error::Result<Self>
+    where
+        T: ArrowPrimitiveType,
+    {
+        let mut builder = PrimitiveBuilder::<T>::new();
+        for s in slice {
+            builder.append_value(*s);
+        }
+        let array: Arc<dyn Array> = Arc::new(builder.finish());
+        Ok(array.into())
+    }
+
+    /// Converts a slice into a `Column`, marking the value at each position
+    /// as null where `valid` is `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `values` and `valid` have different lengths.
+    pub fn try_from_slice_with_nulls<T>(
+        values: &[T::Native],
+        valid: &[bool],
+    ) -> arrow::error::Result<Self>
+    where
+        T: ArrowPrimitiveType,
+    {
+        if values.len() != valid.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "{} values but {} validity flags",
+                values.len(),
+                valid.len()
+            )));
+        }
+        let mut builder = PrimitiveBuilder::<T>::with_capacity(values.len());
+        builder.append_values(values, valid);
+        let array: Arc<dyn Array> = Arc::new(builder.finish());
+        Ok(array.into())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of bytes of the values in this `Column`: the total
+    /// length of the values of a `Utf8` or `Binary` column, or the number of
+    /// rows times the width of the type of a primitive column. Unlike the
+    /// memory allocated for the arrays, it excludes offsets, null bitmaps, and
+    /// unused capacity. A boolean takes one bit.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.arrays
+            .iter()
+            .map(|array| match array.data_type() {
+                DataType::Utf8 => array
+                    .as_any()
+                    .downcast_ref::<StringArray>()
+                    .map_or(0, |arr| arr.iter().flatten().map(str::len).sum()),
+                DataType::Binary => array
+                    .as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .map_or(0, |arr| arr.iter().flatten().map(<[u8]>::len).sum()),
+                DataType::Boolean => (array.len() + 7) / 8,
+                data_type => data_type.primitive_width().unwrap_or_default() * array.len(),
+            })
+            .sum()
+    }
+
+    /// Returns this `Column` with narrow signed integers widened to `Int64`
+    /// and `Float32` to `Float64`, the types read for `ColumnType::Int64` and
+    /// `ColumnType::Float64`, or this `Column` itself if it needs no widening.
+    fn widened(&self) -> Cow<'_, Self> {
+        let wide_type = match self.data_type() {
+            Some(DataType::Int32 | DataType::Int16 | DataType::Int8) => DataType::Int64,
+            Some(DataType::Float32) => DataType::Float64,
+            _ => return Cow::Borrowed(self),
+        };
+        let arrays = self
+            .arrays
+            .iter()
+            .map(|array| arrow::compute::cast(array, &wide_type).expect("widening should not fail"))
+            .collect();
+        Cow::Owned(Self {
+            arrays,
+            cumlen: self.cumlen.clone(),
+            len: self.len,
+        })
+    }
+
+    /// Returns the data type of the arrays in this `Column`, or `None` if it
+    /// has no arrays.
+    fn data_type(&self) -> Option<&DataType> {
+        self.arrays.first().map(Array::data_type)
+    }
+
+    /// Returns the `n` largest values among the rows designated by `selected`,
+    /// with their row indices, in descending order.
+    ///
+    /// Null values are skipped. Equal values are ordered by row index. For
+    /// floating-point numbers, the order is the IEEE 754 total order, e.g.,
+    /// NaN is larger than infinity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn n_largest_values<T>(
+        &self,
+        selected: &[usize],
+        n: usize,
+    ) -> Result<Vec<(usize, T::Native)>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        self.n_extreme_values::<T>(selected.iter().copied(), n, true)
+    }
+
+    /// Returns the `n` smallest values among the rows designated by
+    /// `selected`, with their row indices, in ascending order.
+    ///
+    /// Null values are skipped, and equal values are ordered by row index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn n_smallest_values<T>(
+        &self,
+        selected: &[usize],
+        n: usize,
+    ) -> Result<Vec<(usize, T::Native)>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        self.n_extreme_values::<T>(selected.iter().copied(), n, false)
+    }
+
+    /// Returns the `n` largest or smallest values among the rows in
+    /// `selected`, keeping at most `n` values in a heap at a time.
+    fn n_extreme_values<T>(
+        &self,
+        selected: impl Iterator<Item = usize>,
+        n: usize,
+        largest: bool,
+    ) -> Result<Vec<(usize, T::Native)>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        let mut heap = BinaryHeap::with_capacity(n.min(self.len()));
+        for row in selected {
+            let Some((arr, inner_index)) = self.locate(row) else {
+                continue;
+            };
+            let Some(typed_arr) = arr.as_any().downcast_ref::<PrimitiveArray<T>>() else {
+                return Err(TypeError());
+            };
+            if typed_arr.is_null(inner_index) {
+                continue;
+            }
+            let ranked = Ranked {
+                row,
+                value: typed_arr.value(inner_index),
+                largest,
+            };
+            if heap.len() < n {
+                heap.push(ranked);
+            } else if heap.peek().is_some_and(|worst| ranked < *worst) {
+                heap.pop();
+                heap.push(ranked);
+            }
+        }
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|ranked| (ranked.row, ranked.value))
+            .collect())
+    }
+
+    /// Returns the array holding the row at `index` and the index of the row
+    /// within the array.
+    fn locate(&self, index: usize) -> Option<(&Arc<dyn Array>, usize)> {
+        if index >= self.len() {
+            return None;
+        }
+        let (array_index, inner_index) = match self.cumlen.binary_search(&index) {
+            Ok(i) => (i, 0),
+            Err(i) => (i - 1, index - self.cumlen[i - 1]),
+        };
+        Some((&self.arrays[array_index], inner_index))
+    }
+
+    /// Returns an iterator over all the values in this `Column` as
+    /// `column_type`, walking each array in turn.
+    fn cells(&self, column_type: ColumnType) -> Result<Cells<'_>, TypeError> {
+        let cells: Cells = match column_type {
+            ColumnType::Int64 => Box::new(self.iter::<Int64Array>()?.map(|v| v.map(Element::Int))),
+            ColumnType::Float64 => {
+                Box::new(self.iter::<Float64Array>()?.map(|v| v.map(Element::Float)))
+            }
+            ColumnType::DateTime => Box::new(self.iter::<Int64Array>()?.map(|v| {
+                v.map(|v| Element::DateTime(chrono::DateTime::from_timestamp_nanos(v).naive_utc()))
+            })),
+            ColumnType::IpAddr => Box::new(
+                self.iter::<UInt32Array>()?
+                    .map(|v| v.map(|v| Element::IpAddr(Ipv4Addr::from(v).into()))),
+            ),
+            ColumnType::UInt64 | ColumnType::Enum => {
+                Box::new(self.iter::<UInt64Array>()?.map(|v| v.map(Element::UInt)))
+            }
+            ColumnType::Utf8 => Box::new(
+                self.iter::<StringArray>()?
+                    .map(|v| v.map(|v| Element::Text(v.to_string()))),
+            ),
+            ColumnType::Binary => Box::new(
+                self.iter::<BinaryArray>()?
+                    .map(|v| v.map(|v| Element::Binary(v.to_vec()))),
+            ),
+            ColumnType::Boolean => Box::new(
+                self.iter::<BooleanArray>()?
+                    .map(|v| v.map(|v| Element::Enum(v.to_string()))),
+            ),
+        };
+        Ok(cells)
+    }
+
+    /// Returns `true` if all the non-null values among the rows designated by
+    /// `rows`, or all the rows if `rows` is `None`, are equal. An empty or
+    /// all-null column is constant. Floating-point values are compared in the
+    /// IEEE 754 total order, so NaNs with the same bits are equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arrays of this `Column` have different data types.
+    #[must_use]
+    pub fn is_constant(&self, rows: Option<&[usize]>) -> bool {
+        let non_null = |row: usize| {
+            let (arr, inner_index) = self.locate(row)?;
+            arr.is_valid(inner_index).then_some((arr, inner_index))
+        };
+        let mut values: Box<dyn Iterator<Item = (&Arc<dyn Array>, usize)>> = match rows {
+            Some(rows) => Box::new(rows.iter().filter_map(move |&row| non_null(row))),
+            None => Box::new((0..self.len()).filter_map(non_null)),
+        };
+        let Some((first, first_index)) = values.next() else {
+            return true;
+        };
+        let mut comparators: HashMap<*const u8, DynComparator> = HashMap::new();
+        values.all(|(arr, inner_index)| {
+            let compare = comparators
+                .entry(Arc::as_ptr(arr).cast::<u8>())
+                .or_insert_with(|| {
+                    make_comparator(first.as_ref(), arr.as_ref(), SortOptions::default())
+                        .expect("arrays of a column should have the same data type")
+                });
+            compare(first_index, inner_index).is_eq()
+        })
+    }
+
+    /// Counts the values among the rows designated by `rows` in `num_bins`
+    /// bins of equal width between `min` and `max`, returning a
+    /// `(bin_start, bin_end, count)` triple for each bin in order.
+    ///
+    /// Each bin includes its start and excludes its end, except the last,
+    /// which includes `max`. Nulls, NaNs, and values outside `[min, max]`
+    /// are not counted. No bin is returned if `num_bins` is zero or `min` is
+    /// not less than `max`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` is not of a primitive integer or
+    /// floating-point type.
+    pub fn histogram(
+        &self,
+        rows: &[usize],
+        num_bins: usize,
+        min: f64,
+        max: f64,
+    ) -> Result<Vec<(f64, f64, usize)>, TypeError> {
+        if num_bins == 0 || min >= max || !min.is_finite() || !max.is_finite() {
+            return Ok(Vec::new());
+        }
+        #[allow(clippy::cast_precision_loss)] // the number of bins is small
+        let edge = |i: usize| {
+            if i == num_bins {
+                max
+            } else {
+                min + (max - min) * (i as f64 / num_bins as f64)
+            }
+        };
+        let mut counts = vec![0; num_bins];
+        for &row in rows {
+            let Some(value) = self.f64_at(row)? else {
+                continue;
+            };
+            if !(min..=max).contains(&value) {
+                continue;
+            }
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_precision_loss,
+                clippy::cast_sign_loss
+            )] // in [0, num_bins]
+            let mut bin = (((value - min) / (max - min)) * num_bins as f64) as usize;
+            // Correct rounding errors so that the bins agree with their edges.
+            bin = bin.min(num_bins - 1);
+            if value < edge(bin) {
+                bin -= 1;
+            } else if bin + 1 < num_bins && edge(bin + 1) <= value {
+                bin += 1;
+            }
+            counts[bin] += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (edge(i), edge(i + 1), count))
+            .collect())
+    }
+
+    /// Returns the value at `index` as `column_type`, or `None` if it is null
+    /// or out of range.
+    fn element_at(
+        &self,
+        index: usize,
+        column_type: ColumnType,
+    ) -> Result<Option<Element>, TypeError> {
+        let Some((arr, inner_index)) = self.locate(index) else {
+            return Ok(None);
+        };
+        if arr.is_null(inner_index) {
+            return Ok(None);
+        }
+        let any = arr.as_any();
+        let element = match column_type {
+            ColumnType::Int64 => any
+                .downcast_ref::<Int64Array>()
+                .map(|a| Element::Int(a.value(inner_index))),
+            ColumnType::Float64 => any
+                .downcast_ref::<Float64Array>()
+                .map(|a| Element::Float(a.value(inner_index))),
+            ColumnType::DateTime => any.downcast_ref::<Int64Array>().map(|a| {
+                Element::DateTime(
+                    chrono::DateTime::from_timestamp_nanos(a.value(inner_index)).naive_utc(),
+                )
+            }),
+            ColumnType::IpAddr => any
+                .downcast_ref::<UInt32Array>()
+                .map(|a| Element::IpAddr(Ipv4Addr::from(a.value(inner_index)).into())),
+            ColumnType::UInt64 | ColumnType::Enum => any
+                .downcast_ref::<UInt64Array>()
+                .map(|a| Element::UInt(a.value(inner_index))),
+            ColumnType::Utf8 => any
+                .downcast_ref::<StringArray>()
+                .map(|a| Element::Text(a.value(inner_index).to_string())),
+            ColumnType::Binary => any
+                .downcast_ref::<BinaryArray>()
+                .map(|a| Element::Binary(a.value(inner_index).to_vec())),
+            ColumnType::Boolean => any
+                .downcast_ref::<BooleanArray>()
+                .map(|a| Element::Enum(a.value(inner_index).to_string())),
+        };
+        element.map(Some).ok_or(TypeError())
+    }
+
+    /// Returns the rows, among those designated by `selected`, whose IPv4
+    /// addresses belong to the subnet `network`/`prefix_len`. A `prefix_len`
+    /// greater than 32 is treated as 32. Null values never match.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not a `UInt32` column of IPv4 addresses.
+    pub fn rows_in_subnet(
+        &self,
+        selected: &[usize],
+        network: Ipv4Addr,
+        prefix_len: u8,
+    ) -> Result<Vec<usize>, TypeError> {
+        let mask = u32::MAX
+            .checked_shl(32 - u32::from(prefix_len.min(32)))
+            .unwrap_or(0);
+        let network = u32::from(network) & mask;
+        let mut matched = Vec::new();
+        for &row in selected {
+            let Some((arr, inner_index)) = self.locate(row) else {
+                continue;
+            };
+            let Some(typed_arr) = arr.as_any().downcast_ref::<UInt32Array>() else {
+                return Err(TypeError());
+            };
+            if typed_arr.is_valid(inner_index) && typed_arr.value(inner_index) & mask == network {
+                matched.push(row);
+            }
+        }
+        Ok(matched)
+    }
+
+    /// Compares each value in this `Column` of primitive type with `value`,
+    /// returning a `Column` of booleans of the same length. A null value
+    /// results in a null.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn compare_scalar_primitive<T>(
+        &self,
+        op: CompareOp,
+        value: T::Native,
+    ) -> Result<Column, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        let scalar = PrimitiveArray::<T>::new_scalar(value);
+        let arrays = self
+            .arrays
+            .iter()
+            .map(|arr| {
+                let arr = arr
+                    .as_any()
+                    .downcast_ref::<PrimitiveArray<T>>()
+                    .ok_or(TypeError())?;
+                let result = match op {
+                    CompareOp::Eq => cmp::eq(arr, &scalar),
+                    CompareOp::Ne => cmp::neq(arr, &scalar),
+                    CompareOp::Lt => cmp::lt(arr, &scalar),
+                    CompareOp::LtEq => cmp::lt_eq(arr, &scalar),
+                    CompareOp::Gt => cmp::gt(arr, &scalar),
+                    CompareOp::GtEq => cmp::gt_eq(arr, &scalar),
+                };
+                result
+                    .map(|r| Arc::new(r) as Arc<dyn Array>)
+                    .map_err(|_| TypeError())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Column {
+            arrays,
+            cumlen: self.cumlen.clone(),
+            len: self.len,
+        })
+    }
+
+    /// Returns `true` if this `Column` of primitive type contains `value`,
+    /// skipping null values. Returns `false` if the type parameter does not
+    /// match with the type of this `Column`.
+    #[must_use]
+    pub fn contains_primitive<T>(&self, value: T::Native) -> bool
+    where
+        T: ArrowPrimitiveType,
+    {
+        self.iter::<PrimitiveArray<T>>()
+            .is_ok_and(|mut iter| iter.any(|v| v.is_some_and(|v| v.is_eq(value))))
+    }
+
+    /// Returns `true` if this `Column` of strings contains `value`, skipping
+    /// null values. Returns `false` if this is not a string column.
+    #[must_use]
+    pub fn contains_string(&self, value: &str) -> bool {
+        self.iter::<StringArray>()
+            .is_ok_and(|mut iter| iter.any(|v| v == Some(value)))
+    }
+
+    /// Returns `true` if this `Column` of binaries contains `value`, skipping
+    /// null values. Returns `false` if this is not a binary column.
+    #[must_use]
+    pub fn contains_binary(&self, value: &[u8]) -> bool {
+        self.iter::<BinaryArray>()
+            .is_ok_and(|mut iter| iter.any(|v| v == Some(value)))
+    }
+
+    /// Converts a dictionary array of labels, such as one exported by
+    /// [`Table::to_record_batch`], into an enum column and its reverse map.
+    /// The dictionary keys become the enum ids, and each id with a non-null
+    /// label is mapped to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the values of the dictionary are not strings.
+    pub fn try_from_dictionary(
+        array: &DictionaryArray<UInt64Type>,
+    ) -> Result<(Self, HashMap<u64, Vec<String>>), TypeError> {
+        let Some(values) = array.values().as_any().downcast_ref::<StringArray>() else {
+            return Err(TypeError());
+        };
+        let reverse_map = values
+            .iter()
+            .enumerate()
+            .filter_map(|(id, label)| Some((id.to_u64()?, vec![label?.to_string()])))
+            .collect();
+        let ids: Arc<dyn Array> = Arc::new(array.keys().clone());
+        Ok((ids.into(), reverse_map))
+    }
+
+    /// Converts an enum column of `u64` ids into a dictionary array keyed by
+    /// the ids. The dictionary value at each id in this column is its labels
+    /// in `reverse_map`, joined as in the statistics, or null if the id is
+    /// not in `reverse_map`. As the values are indexed by id, the dictionary
+    /// has as many values as the largest id plus one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not a `UInt64` column, or an id does not
+    /// fit in `usize`.
+    pub fn to_dictionary(
+        &self,
+        reverse_map: &HashMap<u64, Vec<String>>,
+    ) -> Result<DictionaryArray<UInt64Type>, TypeError> {
+        let keys: UInt64Array = self.iter::<UInt64Array>()?.collect();
+        let mut labels: Vec<Option<String>> = Vec::new();
+        for id in keys.iter().flatten() {
+            let index = id.to_usize().ok_or(TypeError())?;
+            if index >= labels.len() {
+                labels.resize(index.checked_add(1).ok_or(TypeError())?, None);
+            }
+            if labels[index].is_none() {
+                labels[index] = reverse_map.get(&id).map(|labels| labels.join("|"));
+            }
+        }
+        Ok(DictionaryArray::new(
+            keys,
+            Arc::new(StringArray::from(labels)),
+        ))
+    }
+
+    /// Converts an enum column of `u64` ids into a `Utf8` column of the labels
+    /// in `reverse_map`, keeping nulls. An id with more than one label becomes
+    /// its labels joined with `joiner`, or its first label if `joiner` is
+    /// `None`. An id not in `reverse_map`, or without any label, becomes
+    /// `unknown`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this is not a `UInt64` column.
+    pub fn dictionary_decode(
+        &self,
+        reverse_map: &HashMap<u64, Vec<String>>,
+        joiner: Option<&str>,
+        unknown: &str,
+    ) -> Result<Self, TypeError> {
+        let label = |id: u64| match (reverse_map.get(&id).map(Vec::as_slice), joiner) {
+            (None | Some([]), _) => unknown.to_string(),
+            (Some(labels), Some(joiner)) => labels.join(joiner),
+            (Some([first, ..]), None) => first.clone(),
+        };
+        let arrays = self
+            .arrays
+            .iter()
+            .map(|arr| {
+                let typed_arr = arr
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .ok_or(TypeError())?;
+                let labels: StringArray = typed_arr.iter().map(|id| id.map(label)).collect();
+                Ok(Arc::new(labels) as Arc<dyn Array>)
+            })
+            .collect::<Result<_, TypeError>>()?;
+        Ok(Self {
+            arrays,
+            cumlen: self.cumlen.clone(),
+            len: self.len,
+        })
+    }
+
+    /// Concatenates the arrays of this `Column` into one, or returns `None` if
+    /// it has no arrays.
+    fn concat(&self) -> Result<Option<Arc<dyn Array>>, ArrowError> {
+        match self.arrays.as_slice() {
+            [] => Ok(None),
+            [array] => Ok(Some(array.clone())),
+            arrays => {
+                let arrays: Vec<&dyn Array> = arrays.iter().map(AsRef::as_ref).collect();
+                arrow::compute::concat(&arrays).map(Some)
+            }
+        }
+    }
+
+    /// Splits the arrays of this `Column` anew into arrays of `chunk_size`
+    /// rows, or concatenates them into one if `chunk_size` is zero.
+    fn rechunk(&mut self, chunk_size: usize) -> Result<(), ArrowError> {
+        self.compact()?;
+        if chunk_size == 0 || self.len <= chunk_size {
+            return Ok(());
+        }
+        let Some(array) = self.arrays.pop() else {
+            return Ok(());
+        };
+        self.cumlen = vec![0];
+        let mut offset = 0;
+        while offset < self.len {
+            let len = chunk_size.min(self.len - offset);
+            self.arrays.push(array.slice(offset, len));
+            offset += len;
+            self.cumlen.push(offset);
+        }
+        Ok(())
+    }
+
+    /// Concatenates the arrays of this `Column` into one.
+    fn compact(&mut self) -> Result<(), ArrowError> {
+        if let Some(array) = self.concat()? {
+            self.arrays = vec![array];
+            self.cumlen = vec![0, self.len];
+        }
+        Ok(())
+    }
+
+    /// Returns the number of null values in this `Column`.
+    #[must_use]
+    pub fn null_count(&self) -> usize {
+        self.arrays.iter().map(Array::null_count).sum()
+    }
+
+    /// Returns the indices of the rows whose values are null, in ascending
+    /// order.
+    #[must_use]
+    pub fn null_rows(&self) -> Vec<usize> {
+        self.arrays
+            .iter()
+            .zip(self.cumlen.iter())
+            .filter(|(arr, _)| arr.null_count() > 0)
+            .flat_map(|(arr, &offset)| {
+                (0..arr.len())
+                    .filter(|&i| arr.is_null(i))
+                    .map(move |i| offset + i)
+            })
+            .collect()
+    }
+
+    /// Return the value specified by the index as type T, or `None` if it is
+    /// null or out of range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if it's fail to convert the value to the specified type
+    pub fn primitive_try_get<T>(&self, index: usize) -> Result<Option<T::Native>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        let Some((arr, inner_index)) = self.locate(index) else {
+            return Ok(None);
+        };
+        let Some(typed_arr) = arr.as_any().downcast_ref::<PrimitiveArray<T>>() else {
+            return Err(TypeError());
+        };
+        Ok(typed_arr
+            .is_valid(inner_index)
+            .then(|| typed_arr.value(inner_index)))
+    }
+
+    /// Return the value specified by the index as byte array, or `None` if it
+    /// is null or out of range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if it's fail to convert the value to byte array
+    pub fn binary_try_get(&self, index: usize) -> Result<Option<&[u8]>, TypeError> {
+        let Some((arr, inner_index)) = self.locate(index) else {
+            return Ok(None);
+        };
+        let Some(typed_arr) = arr.as_any().downcast_ref::<BinaryArray>() else {
+            return Err(TypeError());
+        };
+        Ok(typed_arr
+            .is_valid(inner_index)
+            .then(|| typed_arr.value(inner_index)))
+    }
+
+    /// Return the value specified by the index as string, or `None` if it is
+    /// null or out of range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if it's fail to convert the value to string
+    pub fn string_try_get(&self, index: usize) -> Result<Option<&str>, TypeError> {
+        let Some((arr, inner_index)) = self.locate(index) else {
+            return Ok(None);
+        };
+        let Some(typed_arr) = arr.as_any().downcast_ref::<StringArray>() else {
+            return Err(TypeError());
+        };
+        Ok(typed_arr
+            .is_valid(inner_index)
+            .then(|| typed_arr.value(inner_index)))
+    }
+
+    /// Returns `true` if the value at `index` is null, and `false` if it is
+    /// valid or out of range.
+    fn is_null_at(&self, index: usize) -> bool {
+        self.locate(index)
+            .is_some_and(|(arr, inner_index)| arr.is_null(inner_index))
+    }
+
+    /// Returns the numeric value at `index` as `f64`, or `None` if it is null
+    /// or out of range.
+    fn f64_at(&self, index: usize) -> Result<Option<f64>, TypeError> {
+        let Some((arr, inner_index)) = self.locate(index) else {
+            return Ok(None);
+        };
+        if arr.is_null(inner_index) {
+            return Ok(None);
+        }
+        let any = arr.as_any();
+        // Reads the value from an array of type `$t`, rounding it to `f64`.
+        macro_rules! value {
+            ($t:ty) => {
+                any.downcast_ref::<$t>()
+                    .and_then(|a| a.value(inner_index).to_f64())
+            };
+        }
+        let value = match arr.data_type() {
+            DataType::Int8 => value!(Int8Array),
+            DataType::Int16 => value!(Int16Array),
+            DataType::Int32 => value!(Int32Array),
+            DataType::Int64 => value!(Int64Array),
+            DataType::UInt8 => value!(UInt8Array),
+            DataType::UInt16 => value!(UInt16Array),
+            DataType::UInt32 => value!(UInt32Array),
+            DataType::UInt64 => value!(UInt64Array),
+            DataType::Float32 => value!(Float32Array),
+            DataType::Float64 => value!(Float64Array),
+            _ => None,
+        };
+        value.map(Some).ok_or(TypeError())
+    }
+
+    /// Returns the values of this `Column` of primitive type, with nulls
+    /// replaced by the default value of the type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn to_vec_primitive<T>(&self) -> Result<Vec<T::Native>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        Ok(self
+            .iter::<PrimitiveArray<T>>()?
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    /// Returns the values of this `Column` of primitive type, with `None` for
+    /// nulls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn to_vec_primitive_opt<T>(&self) -> Result<Vec<Option<T::Native>>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        Ok(self.iter::<PrimitiveArray<T>>()?.collect())
+    }
+
+    /// Returns the values of this `Column` of binaries, with nulls replaced by
+    /// empty byte sequences.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of binaries.
+    pub fn to_vec_binary(&self) -> Result<Vec<Vec<u8>>, TypeError> {
+        Ok(self
+            .iter::<BinaryArray>()?
+            .map(|v| v.unwrap_or_default().to_vec())
+            .collect())
+    }
+
+    /// Returns the values of this `Column` of binaries, with `None` for nulls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of binaries.
+    pub fn to_vec_binary_opt(&self) -> Result<Vec<Option<Vec<u8>>>, TypeError> {
+        Ok(self
+            .iter::<BinaryArray>()?
+            .map(|v| v.map(<[u8]>::to_vec))
+            .collect())
+    }
+
+    /// Returns the values of this `Column` of strings, with nulls replaced by
+    /// empty strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of strings.
+    pub fn to_vec_string(&self) -> Result<Vec<String>, TypeError> {
+        Ok(self
+            .iter::<StringArray>()?
+            .map(|v| v.unwrap_or_default().to_string())
+            .collect())
+    }
+
+    /// Returns the values of this `Column` of strings, with `None` for nulls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of strings.
+    pub fn to_vec_string_opt(&self) -> Result<Vec<Option<String>>, TypeError> {
+        Ok(self
+            .iter::<StringArray>()?
+            .map(|v| v.map(str::to_string))
+            .collect())
+    }
+
+    /// Returns the distinct non-null values of this `Column` of primitive
+    /// type in the order they first appear.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn unique_primitive<T>(&self) -> Result<Vec<T::Native>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+        T::Native: Eq + std::hash::Hash,
+    {
+        let mut seen = HashSet::new();
+        Ok(self
+            .iter::<PrimitiveArray<T>>()?
+            .flatten()
+            .filter(|v| seen.insert(*v))
+            .collect())
+    }
+
+    /// Returns the distinct non-null values of this `Column` of strings in the
+    /// order they first appear.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of strings.
+    pub fn unique_strings(&self) -> Result<Vec<String>, TypeError> {
+        let mut seen = HashSet::new();
+        Ok(self
+            .iter::<StringArray>()?
+            .flatten()
+            .filter(|v| seen.insert(*v))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Appends `values` to this `Column` as a new array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` already holds values of a type other
+    /// than `T`.
+    pub fn append_slice<T>(&mut self, values: &[T::Native]) -> Result<(), TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        let array: Arc<dyn Array> = Arc::new(PrimitiveArray::<T>::from_iter_values(
+            values.iter().copied(),
+        ));
+        self.try_append(&mut array.into())
+    }
+
+    /// Returns a `Column` with the values of this `Column` of primitive type
+    /// clamped to `[lo, hi]`, keeping nulls. A value less than `lo` becomes
+    /// `lo`, and one greater than `hi` becomes `hi`; NaN is kept as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn clamp_primitive<T>(&self, lo: T::Native, hi: T::Native) -> Result<Self, TypeError>
+    where
+        T: ArrowPrimitiveType,
+        T::Native: PartialOrd,
+    {
+        let arrays = self
+            .arrays
+            .iter()
+            .map(|arr| {
+                let arr = arr
+                    .as_any()
+                    .downcast_ref::<PrimitiveArray<T>>()
+                    .ok_or(TypeError())?;
+                let clamped = arr.unary::<_, T>(|v| {
+                    if v < lo {
+                        lo
+                    } else if v > hi {
+                        hi
+                    } else {
+                        v
+                    }
+                });
+                Ok(Arc::new(clamped) as Arc<dyn Array>)
+            })
+            .collect::<Result<_, TypeError>>()?;
+        Ok(Self {
+            arrays,
+            cumlen: self.cumlen.clone(),
+            len: self.len,
+        })
+    }
+
+    /// Returns the `len` rows starting at `offset`, or fewer if this `Column`
+    /// ends earlier, sharing the arrays of this `Column`. Only the arrays
+    /// overlapping the range are kept, sliced to it. A slice without any row
+    /// keeps the data type of this `Column`.
+    #[must_use]
+    pub fn slice(&self, offset: usize, len: usize) -> Self {
+        let end = offset.saturating_add(len);
+        let mut sliced = Self {
+            arrays: Vec::new(),
+            cumlen: vec![0],
+            len: 0,
+        };
+        for (array, (&start, &stop)) in self
+            .arrays
+            .iter()
+            .zip(self.cumlen.iter().zip(self.cumlen.iter().skip(1)))
+        {
+            if stop <= offset || end <= start {
+                continue;
+            }
+            let (from, to) = (offset.max(start), end.min(stop));
+            sliced.arrays.push(array.slice(from - start, to - from));
+            sliced.len += to - from;
+            sliced.cumlen.push(sliced.len);
+        }
+        if let (true, Some(array)) = (sliced.arrays.is_empty(), self.arrays.first()) {
+            // Keep the data type even if there is no row.
+            sliced.arrays.push(array.slice(0, 0));
+            sliced.cumlen.push(0);
+        }
+        sliced
+    }
+
+    /// Moves all the values of `other` into this `Column`, leaving `other`
+    /// empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `other` holds values of a type other than that of
+    /// this `Column`. Neither `Column` is modified in that case.
+    pub fn try_append(&mut self, other: &mut Self) -> Result<(), TypeError> {
+        if let (Some(s), Some(o)) = (self.data_type(), other.data_type()) {
+            if s != o {
+                return Err(TypeError());
+            }
+        }
+        if self.cumlen.is_empty() {
+            // A default `Column` has no starting offset.
+            self.cumlen.push(0);
+        }
+        self.arrays.append(&mut other.arrays);
+        let len = self.len;
+        self.cumlen
+            .extend(other.cumlen.iter().skip(1).map(|v| v + len));
+        self.len += other.len;
+        other.len = 0;
+        Ok(())
+    }
+
+    /// Converts the values of an `Int64` column to `f64`, e.g., to append the
+    /// batches of a column promoted by [`Reader::with_float_promotion`] to a
+    /// column built from the batches read before. A `Float64` column, or one
+    /// without any array, is left as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` is neither `Int64` nor `Float64`.
+    pub fn promote_to_float64(&mut self) -> Result<(), TypeError> {
+        match self.data_type() {
+            Some(DataType::Int64) => {}
+            Some(DataType::Float64) | None => return Ok(()),
+            Some(_) => return Err(TypeError()),
+        }
+        for array in &mut self.arrays {
+            *array = arrow::compute::cast(array, &DataType::Float64).map_err(|_| TypeError())?;
+        }
+        Ok(())
+    }
+
+    /// Creates an iterator iterating over all the cells in this `Column`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    #[allow(clippy::iter_not_returning_iterator)]
+    // TODO: resolve this clippy warning
+    pub fn iter<'a, T>(&'a self) -> Result<Flatten<vec::IntoIter<&'a T>>, TypeError>
+    where
+        T: Array + 'static,
+        &'a T: IntoIterator,
+    {
+        let mut arrays: Vec<&T> = Vec::with_capacity(self.arrays.len());
+        for arr in &self.arrays {
+            let Some(typed_arr) = arr.as_any().downcast_ref::<T>() else {
+                return Err(TypeError());
+            };
+            arrays.push(typed_arr);
+        }
+        Ok(arrays.into_iter().flatten())
+    }
+
+    /// Creates an iterator iterating over a subset of the cells in this
+    /// `Column` of primitive type, designated by `selected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn primitive_iter<'a, 'b, T>(
+        &'a self,
+        selected: &'b [usize],
+    ) -> Result<PrimitiveIter<'a, 'b, T>, TypeError>
+    where
+        T: ArrowPrimitiveType,
+    {
+        Ok(PrimitiveIter::new(self, selected.iter()))
+    }
+
+    /// Creates an iterator iterating over a subset of the cells in this
+    /// `Column` of binaries, designated by `selected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn binary_iter<'a, 'b>(
+        &'a self,
+        selected: &'b [usize],
+    ) -> Result<BinaryIter<'a, 'b>, TypeError> {
+        Ok(BinaryIter::new(self, selected.iter()))
+    }
+
+    /// Creates an iterator iterating over the non-null values of a subset of
+    /// the cells in this `Column` of booleans, designated by `selected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if this `Column` does not consist of booleans.
+    pub(crate) fn boolean_iter<'a>(
+        &'a self,
+        selected: &'a [usize],
+    ) -> Result<impl Iterator<Item = bool> + 'a, TypeError> {
+        if self
+            .arrays
+            .iter()
+            .any(|arr| arr.as_any().downcast_ref::<BooleanArray>().is_none())
+        {
+            return Err(TypeError());
+        }
+        Ok(selected.iter().filter_map(|&row| {
+            let (arr, inner_index) = self.locate(row)?;
+            let arr = arr.as_any().downcast_ref::<BooleanArray>()?;
+            arr.is_valid(inner_index).then(|| arr.value(inner_index))
+        }))
+    }
+
+    /// Creates an iterator iterating over a subset of the cells in this
+    /// `Column` of strings, designated by `selected`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type parameter does not match with the type of
+    /// this `Column`.
+    pub fn string_iter<'a, 'b>(
+        &'a self,
+        selected: &'b [usize],
+    ) -> Result<StringIter<'a, 'b>, TypeError> {
+        Ok(StringIter::new(self, selected.iter()))
+    }
+}
+
+impl Column {
+    /// Returns `true` if this `Column` equals `other`, except that floating
+    /// point values are equal if they differ by at most `epsilon`, and NaN
+    /// equals NaN. Columns of other types are compared as by `==`.
+    #[must_use]
+    pub fn approx_eq(&self, other: &Column, epsilon: f64) -> bool {
+        fn close(x: Option<f64>, y: Option<f64>, epsilon: f64) -> bool {
+            match (x, y) {
+                (Some(x), Some(y)) => (x.is_nan() && y.is_nan()) || (x - y).abs() <= epsilon,
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        if self.len() != other.len() || self.data_type() != other.data_type() {
+            return false;
+        }
+        match self.data_type() {
+            Some(DataType::Float32) => {
+                match (self.iter::<Float32Array>(), other.iter::<Float32Array>()) {
+                    (Ok(xs), Ok(ys)) => xs
+                        .zip(ys)
+                        .all(|(x, y)| close(x.map(f64::from), y.map(f64::from), epsilon)),
+                    _ => false,
+                }
+            }
+            Some(DataType::Float64) => {
+                match (self.iter::<Float64Array>(), other.iter::<Float64Array>()) {
+                    (Ok(xs), Ok(ys)) => xs.zip(ys).all(|(x, y)| close(x, y, epsilon)),
+                    _ => false,
+                }
+            }
+            _ => self == other,
+        }
+    }
+}
+
+impl PartialEq for Column {
+    fn eq(&self, other: &Self) -> bool {
+        let data_type = match (self.arrays.first(), other.arrays.first()) {
+            (Some(x_arr), Some(y_arr)) => {
+                if x_arr.to_data().data_type() == y_arr.to_data().data_type() {
+                    x_arr.to_data().data_type().clone()
+                } else {
+                    return false;
+                }
+            }
+            (Some(_), None) | (None, Some(_)) => return false,
+            (None, None) => return true,
+        };
+        if self.len() != other.len() {
+            return false;
+        }
+
+        match data_type {
+            DataType::Int8 => self
+                .iter::<Int8Array>()
                 .expect("invalid array")
                 .zip(other.iter::<Int8Array>().expect("invalid array"))
                 .all(|(x, y)| x == y),
@@ -555,140 +3227,2139 @@ impl PartialEq for Column {
                 .all(|(x, y)| x == y),
             DataType::Utf8 => self
                 .iter::<StringArray>()
-                .expect("invalid array")
-                .zip(other.iter::<StringArray>().expect("invalid array"))
-                .all(|(x, y)| x == y),
-            DataType::Binary => self
-                .iter::<BinaryArray>()
-                .expect("invalid array")
-                .zip(other.iter::<BinaryArray>().expect("invalid array"))
-                .all(|(x, y)| x == y),
-            _ => unimplemented!(),
+                .expect("invalid array")
+                .zip(other.iter::<StringArray>().expect("invalid array"))
+                .all(|(x, y)| x == y),
+            DataType::Binary => self
+                .iter::<BinaryArray>()
+                .expect("invalid array")
+                .zip(other.iter::<BinaryArray>().expect("invalid array"))
+                .all(|(x, y)| x == y),
+            DataType::Boolean => self
+                .iter::<BooleanArray>()
+                .expect("invalid array")
+                .zip(other.iter::<BooleanArray>().expect("invalid array"))
+                .all(|(x, y)| x == y),
+            _ => unimplemented!(),
+        }
+    }
+}
+
+impl From<Arc<dyn Array>> for Column {
+    fn from(array: Arc<dyn Array>) -> Self {
+        let len = array.len();
+        Self {
+            arrays: vec![array],
+            cumlen: vec![0, len],
+            len,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct TypeError();
+
+/// Returns the next value of `column` among `selected`, reading each row
+/// with `get`. A null yields the default value of the type, or is skipped if
+/// `skip_nulls` is `true`. The iteration stops at a row out of range or of
+/// another type.
+fn next_selected<'a, V, F>(
+    column: &'a Column,
+    selected: &mut slice::Iter<'_, usize>,
+    skip_nulls: bool,
+    get: F,
+) -> Option<V>
+where
+    V: Default,
+    F: Fn(&'a Column, usize) -> Result<Option<V>, TypeError>,
+{
+    loop {
+        let &row = selected.next()?;
+        match get(column, row) {
+            Ok(Some(value)) => return Some(value),
+            Ok(None) if column.is_null_at(row) => {
+                if !skip_nulls {
+                    return Some(V::default());
+                }
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// An iterator over the values of a `Column` of primitive type at the
+/// selected rows. A null yields the default value of the type, e.g., 0,
+/// unless [`PrimitiveIter::skip_nulls`] is called.
+pub struct PrimitiveIter<'a, 'b, T: ArrowPrimitiveType> {
+    column: &'a Column,
+    selected: slice::Iter<'b, usize>,
+    skip_nulls: bool,
+    _t_marker: PhantomData<T>,
+}
+
+impl<'a, 'b, T> PrimitiveIter<'a, 'b, T>
+where
+    T: ArrowPrimitiveType,
+{
+    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
+        Self {
+            column,
+            selected,
+            skip_nulls: false,
+            _t_marker: PhantomData,
+        }
+    }
+
+    /// Skips nulls instead of yielding the default value for them.
+    #[must_use]
+    pub fn skip_nulls(mut self) -> Self {
+        self.skip_nulls = true;
+        self
+    }
+}
+
+impl<T> Iterator for PrimitiveIter<'_, '_, T>
+where
+    T: ArrowPrimitiveType,
+{
+    type Item = T::Native;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_selected(
+            self.column,
+            &mut self.selected,
+            self.skip_nulls,
+            Column::primitive_try_get::<T>,
+        )
+    }
+}
+
+/// An iterator over the values of a `Column` of binaries at the selected
+/// rows. A null yields an empty byte sequence, unless
+/// [`BinaryIter::skip_nulls`] is called.
+pub struct BinaryIter<'a, 'b> {
+    column: &'a Column,
+    selected: slice::Iter<'b, usize>,
+    skip_nulls: bool,
+}
+
+impl<'a, 'b> BinaryIter<'a, 'b> {
+    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
+        Self {
+            column,
+            selected,
+            skip_nulls: false,
+        }
+    }
+
+    /// Skips nulls instead of yielding empty byte sequences for them.
+    #[must_use]
+    pub fn skip_nulls(mut self) -> Self {
+        self.skip_nulls = true;
+        self
+    }
+}
+
+impl<'a> Iterator for BinaryIter<'a, '_> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_selected(
+            self.column,
+            &mut self.selected,
+            self.skip_nulls,
+            Column::binary_try_get,
+        )
+    }
+}
+
+/// An iterator over the values of a `Column` of strings at the selected
+/// rows. A null yields an empty string, unless [`StringIter::skip_nulls`] is
+/// called.
+pub struct StringIter<'a, 'b> {
+    column: &'a Column,
+    selected: slice::Iter<'b, usize>,
+    skip_nulls: bool,
+}
+
+impl<'a, 'b> StringIter<'a, 'b> {
+    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
+        Self {
+            column,
+            selected,
+            skip_nulls: false,
+        }
+    }
+
+    /// Skips nulls instead of yielding empty strings for them.
+    #[must_use]
+    pub fn skip_nulls(mut self) -> Self {
+        self.skip_nulls = true;
+        self
+    }
+}
+
+impl<'a> Iterator for StringIter<'a, '_> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_selected(
+            self.column,
+            &mut self.selected,
+            self.skip_nulls,
+            Column::string_try_get,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+    use ahash::AHasher;
+    use arrow::datatypes::{Float32Type, Float64Type, Int32Type, UInt32Type, UInt64Type};
+    use chrono::NaiveDate;
+    use std::hash::{Hash, Hasher};
+    use std::net::IpAddr;
+
+    fn hash(seq: &str) -> u64 {
+        let mut hasher = AHasher::default();
+        seq.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn table_new() {
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), Vec::new(), HashMap::new())
+            .expect("creating an empty `Table` should not fail");
+        assert_eq!(table.num_columns(), 0);
+        assert_eq!(table.num_rows(), 0);
+    }
+
+    #[test]
+    fn column_new() {
+        let column = Column::default();
+        assert_eq!(column.len(), 0);
+        assert_eq!(column.primitive_try_get::<UInt32Type>(0), Ok(None));
+
+        let column = Column::default();
+        assert_eq!(column.len(), 0);
+        assert_eq!(column.string_try_get(0), Ok(None));
+    }
+
+    #[test]
+    fn flag_column() {
+        let mut flags = vec![false; 200];
+        flags[3] = true;
+        flags[150] = true;
+        let dense = FlagColumn::dense(&flags);
+        let sparse = FlagColumn::sparse(&flags);
+        assert!(!dense.is_sparse());
+        assert!(FlagColumn::new(&flags).is_sparse());
+        for column in [&dense, &sparse] {
+            assert_eq!(column.len(), 200);
+            assert_eq!(column.count_set(), 2);
+            assert!(column.is_set(3) && column.is_set(150));
+            assert!(!column.is_set(4) && !column.is_set(200));
+            assert_eq!(column.set_indices().collect::<Vec<_>>(), [3, 150]);
+            assert_eq!(column.iter().collect::<Vec<_>>(), flags);
+        }
+
+        let flags = [true, false, true, true];
+        assert!(!FlagColumn::new(&flags).is_sparse());
+        assert_eq!(
+            FlagColumn::sparse(&flags).iter().collect::<Vec<_>>(),
+            FlagColumn::dense(&flags).iter().collect::<Vec<_>>()
+        );
+        assert!(FlagColumn::new(&[]).is_empty());
+
+        assert_eq!(dense, sparse);
+        assert_eq!(FlagColumn::sparse(&flags), FlagColumn::dense(&flags));
+        assert_ne!(FlagColumn::dense(&flags), FlagColumn::dense(&flags[..3]));
+        assert_ne!(
+            FlagColumn::sparse(&[false, true]),
+            FlagColumn::dense(&[true, false])
+        );
+        assert_eq!(FlagColumn::sparse(&[]), FlagColumn::dense(&[]));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn histogram() {
+        let column = Column::try_from_slice_with_nulls::<Float64Type>(
+            &[0.0, 0.5, 1.0, 2.5, 3.0, 4.0, -1.0, 4.5, f64::NAN, 2.0],
+            &[true, true, true, true, true, true, true, true, true, false],
+        )
+        .unwrap();
+        let rows: Vec<usize> = (0..column.len()).collect();
+        assert_eq!(
+            column.histogram(&rows, 4, 0.0, 4.0).unwrap(),
+            [(0.0, 1.0, 2), (1.0, 2.0, 1), (2.0, 3.0, 1), (3.0, 4.0, 2)]
+        );
+        assert_eq!(
+            column.histogram(&rows[..3], 2, 0.0, 1.0).unwrap(),
+            [(0.0, 0.5, 1), (0.5, 1.0, 2)]
+        );
+        assert!(column.histogram(&rows, 0, 0.0, 4.0).unwrap().is_empty());
+        assert!(column.histogram(&rows, 4, 4.0, 4.0).unwrap().is_empty());
+
+        // Edges that are not exact in binary floating point
+        let column = Column::try_from_slice::<Float64Type>(&[0.1, 0.2, 0.3]).unwrap();
+        for (row, value) in [0.1, 0.2, 0.3].into_iter().enumerate() {
+            let bins = column.histogram(&[row], 3, 0.0, 0.3).unwrap();
+            let (i, &(start, end, _)) =
+                bins.iter().enumerate().find(|(_, bin)| bin.2 == 1).unwrap();
+            assert!(start <= value && (value < end || i == 2));
+        }
+
+        let column = Column::try_from_slice::<Int64Type>(&[1, 2, 3]).unwrap();
+        assert_eq!(
+            column.histogram(&[0, 1, 2], 1, 1.0, 3.0).unwrap(),
+            [(1.0, 3.0, 3)]
+        );
+        let column = Column::try_from_slice_with_nulls::<Float32Type>(
+            &[0.5, 1.5, 3.5, f32::NAN, 2.0],
+            &[true, true, true, true, false],
+        )
+        .unwrap();
+        assert_eq!(
+            column.histogram(&[0, 1, 2, 3, 4], 2, 0.0, 4.0).unwrap(),
+            [(0.0, 2.0, 2), (2.0, 4.0, 1)]
+        );
+        let column: Column = (Arc::new(StringArray::from(vec!["a"])) as Arc<dyn Array>).into();
+        assert!(column.histogram(&[0], 1, 0.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn constant_columns() {
+        let mut constant = Column::try_from_slice::<Int64Type>(&[7, 7]).unwrap();
+        constant
+            .try_append(
+                &mut Column::try_from_slice_with_nulls::<Int64Type>(
+                    &[7, 0, 7],
+                    &[true, false, true],
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let near_constant =
+            Column::try_from_slice::<Float64Type>(&[1.0, 1.0, 1.0, 1.0, 1.000_001]).unwrap();
+        let all_null =
+            Column::try_from_slice_with_nulls::<UInt64Type>(&[1, 2, 3, 4, 5], &[false; 5]).unwrap();
+        let strings = Column::from(
+            Arc::new(StringArray::from(vec!["a", "a", "a", "b", "a"])) as Arc<dyn Array>
+        );
+        assert!(constant.is_constant(None));
+        assert!(!near_constant.is_constant(None));
+        assert!(near_constant.is_constant(Some(&[0, 2, 3])));
+        assert!(all_null.is_constant(None));
+        assert!(!strings.is_constant(None));
+        assert!(strings.is_constant(Some(&[0, 1, 4, 9])));
+        assert!(Column::default().is_constant(None));
+
+        let table = Table::<u64>::new(
+            Arc::new(Schema::empty()),
+            vec![constant, near_constant, all_null, strings],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        assert_eq!(table.constant_columns(), [0, 2]);
+    }
+
+    #[test]
+    fn null_values() {
+        let ints: Arc<dyn Array> = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        let column = Column::from(ints);
+        assert_eq!(column.primitive_try_get::<Int64Type>(0), Ok(Some(1)));
+        assert_eq!(column.primitive_try_get::<Int64Type>(1), Ok(None));
+        assert_eq!(column.primitive_try_get::<Int64Type>(3), Ok(None));
+        let rows = [0, 1, 2];
+        let iter = || column.primitive_iter::<Int64Type>(&rows).unwrap();
+        assert_eq!(iter().collect::<Vec<_>>(), [1, 0, 3]);
+        assert_eq!(iter().skip_nulls().collect::<Vec<_>>(), [1, 3]);
+
+        let strings: Arc<dyn Array> = Arc::new(StringArray::from(vec![None, Some("b")]));
+        let column = Column::from(strings);
+        assert_eq!(column.string_try_get(0), Ok(None));
+        assert_eq!(column.string_try_get(1), Ok(Some("b")));
+        let rows = [0, 1, 0];
+        let iter = || column.string_iter(&rows).unwrap();
+        assert_eq!(iter().collect::<Vec<_>>(), ["", "b", ""]);
+        assert_eq!(iter().skip_nulls().collect::<Vec<_>>(), ["b"]);
+
+        let binaries: Arc<dyn Array> = Arc::new(BinaryArray::from(vec![Some(&b"a"[..]), None]));
+        let column = Column::from(binaries);
+        assert_eq!(column.binary_try_get(1), Ok(None));
+        let rows = [1, 0];
+        let iter = || column.binary_iter(&rows).unwrap();
+        assert_eq!(iter().collect::<Vec<_>>(), [&b""[..], b"a"]);
+        assert_eq!(iter().skip_nulls().collect::<Vec<_>>(), [b"a"]);
+    }
+
+    #[test]
+    fn clamp_primitive() {
+        let mut column =
+            Column::try_from_slice_with_nulls::<Int64Type>(&[-5, 3, 0], &[true, true, false])
+                .unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[12, 10]).unwrap())
+            .unwrap();
+        let clamped = column.clamp_primitive::<Int64Type>(0, 10).unwrap();
+        assert_eq!(
+            clamped.to_vec_primitive_opt::<Int64Type>().unwrap(),
+            [Some(0), Some(3), None, Some(10), Some(10)]
+        );
+        assert!(column.clamp_primitive::<Float64Type>(0.0, 1.0).is_err());
+
+        let column = Column::try_from_slice::<Float64Type>(&[-0.5, 0.25, 1.5, f64::NAN]).unwrap();
+        let clamped = column
+            .clamp_primitive::<Float64Type>(0.0, 1.0)
+            .unwrap()
+            .to_vec_primitive::<Float64Type>()
+            .unwrap();
+        assert_eq!(clamped[..3], [0.0, 0.25, 1.0]);
+        assert!(clamped[3].is_nan());
+    }
+
+    #[test]
+    fn column_total_bytes() {
+        let strings: Arc<dyn Array> = Arc::new(StringArray::from(vec![
+            Some("abc"),
+            None,
+            Some(""),
+            Some("de"),
+        ]));
+        let column = Column::from(strings);
+        assert_eq!(column.total_bytes(), 5);
+        let column = column.slice(1, 3);
+        assert_eq!(column.total_bytes(), 2);
+
+        let mut column = Column::try_from_slice::<UInt32Type>(&[1, 2, 3]).unwrap();
+        column.append_slice::<UInt32Type>(&[4]).unwrap();
+        assert_eq!(column.total_bytes(), 16);
+        assert_eq!(Column::default().total_bytes(), 0);
+    }
+
+    #[test]
+    fn column_try_append() {
+        let mut column = Column::try_from_slice::<Int64Type>(&[1, 2]).unwrap();
+        let mut other = Column::try_from_slice::<Int64Type>(&[3]).unwrap();
+        column.try_append(&mut other).unwrap();
+        assert_eq!(column.to_vec_primitive::<Int64Type>().unwrap(), [1, 2, 3]);
+        assert_eq!(other.len(), 0);
+
+        let mut other = Column::try_from_slice::<Float64Type>(&[4.0]).unwrap();
+        assert_eq!(column.try_append(&mut other), Err(TypeError()));
+        assert_eq!(column.to_vec_primitive::<Int64Type>().unwrap(), [1, 2, 3]);
+        assert_eq!(other.to_vec_primitive::<Float64Type>().unwrap(), [4.0]);
+
+        let mut column = Column::default();
+        column.try_append(&mut other).unwrap();
+        assert_eq!(column.to_vec_primitive::<Float64Type>().unwrap(), [4.0]);
+    }
+
+    #[test]
+    fn column_promote_to_float64() {
+        let parsers = [FieldParser::int64()];
+        let data = [&b"1"[..], b"2", b"3.5", b"4"];
+        let mut reader = Reader::new(data.iter().copied(), 2, &parsers).with_float_promotion();
+        let mut column = Column::default();
+        while let Some(batch) = reader.next_batch().unwrap() {
+            let mut other = Column::from(batch.columns()[0].clone());
+            if reader.promoted_columns().contains(&0) {
+                column.promote_to_float64().unwrap();
+            }
+            column.try_append(&mut other).unwrap();
+        }
+        assert_eq!(
+            column.to_vec_primitive::<Float64Type>().unwrap(),
+            [1.0, 2.0, 3.5, 4.0]
+        );
+        column.promote_to_float64().unwrap();
+        assert_eq!(column.len(), 4);
+
+        let mut column = Column::try_from_slice::<UInt64Type>(&[1]).unwrap();
+        assert_eq!(column.promote_to_float64(), Err(TypeError()));
+    }
+
+    #[test]
+    fn column_append_slice() {
+        let mut column = Column::default();
+        column.append_slice::<Int64Type>(&[1, 2]).unwrap();
+        column.append_slice::<Int64Type>(&[3]).unwrap();
+        column.append_slice::<Int64Type>(&[]).unwrap();
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.to_vec_primitive::<Int64Type>().unwrap(), [1, 2, 3]);
+        assert_eq!(column.append_slice::<UInt32Type>(&[4]), Err(TypeError()));
+        assert_eq!(column.len(), 3);
+    }
+
+    #[test]
+    fn column_unique() {
+        let mut column = Column::try_from_slice_with_nulls::<Int64Type>(
+            &[3, 1, 3, 0, 2],
+            &[true, true, true, false, true],
+        )
+        .unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[1, 5, 2]).unwrap())
+            .unwrap();
+        assert_eq!(
+            column.unique_primitive::<Int64Type>().unwrap(),
+            [3, 1, 2, 5]
+        );
+        assert!(column.unique_primitive::<UInt32Type>().is_err());
+        assert!(column.unique_strings().is_err());
+
+        let strings: Arc<dyn Array> = Arc::new(StringArray::from(vec![
+            Some("b"),
+            Some("a"),
+            None,
+            Some("b"),
+            Some("c"),
+            Some("a"),
+        ]));
+        let column: Column = strings.into();
+        assert_eq!(column.unique_strings().unwrap(), ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn column_to_vec() {
+        let values = [3_i64, -1, 4];
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        assert_eq!(column.to_vec_primitive::<Int64Type>().unwrap(), values);
+        assert_eq!(
+            column.to_vec_primitive_opt::<Int64Type>().unwrap(),
+            [Some(3), Some(-1), Some(4)]
+        );
+        assert!(column.to_vec_primitive::<UInt32Type>().is_err());
+        assert!(column.to_vec_string().is_err());
+
+        let mut column =
+            Column::try_from_slice_with_nulls::<Int64Type>(&values, &[true, false, true]).unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[5]).unwrap())
+            .unwrap();
+        assert_eq!(
+            column.to_vec_primitive::<Int64Type>().unwrap(),
+            [3, 0, 4, 5]
+        );
+        assert_eq!(
+            column.to_vec_primitive_opt::<Int64Type>().unwrap(),
+            [Some(3), None, Some(4), Some(5)]
+        );
+
+        let strings: Arc<dyn Array> = Arc::new(StringArray::from(vec![Some("a"), None]));
+        let column: Column = strings.into();
+        assert_eq!(column.to_vec_string().unwrap(), ["a", ""]);
+        assert_eq!(
+            column.to_vec_string_opt().unwrap(),
+            [Some("a".to_string()), None]
+        );
+        assert!(column.to_vec_binary().is_err());
+
+        let binaries: Arc<dyn Array> = Arc::new(BinaryArray::from(vec![Some(&b"x"[..]), None]));
+        let column: Column = binaries.into();
+        assert_eq!(column.to_vec_binary().unwrap(), [b"x".to_vec(), Vec::new()]);
+        assert_eq!(
+            column.to_vec_binary_opt().unwrap(),
+            [Some(b"x".to_vec()), None]
+        );
+    }
+
+    #[test]
+    fn column_with_nulls() {
+        let column =
+            Column::try_from_slice_with_nulls::<Int64Type>(&[1, 0, 3], &[true, false, true])
+                .unwrap();
+        assert_eq!(column.len(), 3);
+        assert_eq!(column.null_count(), 1);
+        assert_eq!(column.null_rows(), vec![1]);
+        assert_eq!(column.primitive_try_get::<Int64Type>(0), Ok(Some(1)));
+        assert_eq!(column.primitive_try_get::<Int64Type>(2), Ok(Some(3)));
+
+        assert!(Column::try_from_slice_with_nulls::<Int64Type>(&[1, 2], &[true]).is_err());
+    }
+
+    #[test]
+    fn column_approx_eq() {
+        let computed = Column::try_from_slice_with_nulls::<Float64Type>(
+            &[0.1 + 0.2, f64::NAN, 0.0, 1.0],
+            &[true, true, false, true],
+        )
+        .unwrap();
+        let mut expected =
+            Column::try_from_slice_with_nulls::<Float64Type>(&[0.3, f64::NAN], &[true, true])
+                .unwrap();
+        expected
+            .try_append(
+                &mut Column::try_from_slice_with_nulls::<Float64Type>(&[5.0, 1.0], &[false, true])
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_ne!(computed, expected);
+        assert!(computed.approx_eq(&expected, 1e-9));
+        assert!(!computed.approx_eq(&expected, 0.0));
+
+        let no_nulls = Column::try_from_slice::<Float64Type>(&[0.3, f64::NAN, 0.0, 1.0]).unwrap();
+        assert!(!computed.approx_eq(&no_nulls, 1e-9));
+        let single = Column::try_from_slice::<Float32Type>(&[0.3, 0.5]).unwrap();
+        assert!(single.approx_eq(
+            &Column::try_from_slice::<Float32Type>(&[0.3 + 1e-7, 0.5]).unwrap(),
+            1e-6
+        ));
+        assert!(!single.approx_eq(
+            &Column::try_from_slice::<Float64Type>(&[0.3, 0.5]).unwrap(),
+            1.0
+        ));
+
+        let ints = Column::try_from_slice::<Int64Type>(&[1, 2]).unwrap();
+        assert!(ints.approx_eq(&Column::try_from_slice::<Int64Type>(&[1, 2]).unwrap(), 0.0));
+        assert!(!ints.approx_eq(&Column::try_from_slice::<Int64Type>(&[1, 3]).unwrap(), 10.0));
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn to_json_rows() {
+        let schema = Schema::new(vec![
+            Field::new("count", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("addr", DataType::UInt32, false),
+            Field::new("score", DataType::Float64, false),
+            Field::new("time", DataType::Int64, false),
+            Field::new("kind", DataType::UInt64, false),
+            Field::new("raw", DataType::Binary, false),
+        ]);
+        let time = NaiveDate::from_ymd_opt(2019, 9, 22)
+            .unwrap()
+            .and_hms_opt(6, 10, 11)
+            .unwrap();
+        let columns: Vec<Column> = vec![
+            Column::try_from_slice_with_nulls::<Int64Type>(&[1, 2], &[true, false]).unwrap(),
+            Column::from(Arc::new(StringArray::from(vec!["a", "b"])) as Arc<dyn Array>),
+            Column::try_from_slice::<UInt32Type>(&[
+                Ipv4Addr::LOCALHOST.into(),
+                Ipv4Addr::new(10, 0, 0, 1).into(),
+            ])
+            .unwrap(),
+            Column::try_from_slice::<Float64Type>(&[0.5, f64::NAN]).unwrap(),
+            Column::try_from_slice::<Int64Type>(&[
+                time.and_utc().timestamp_nanos_opt().unwrap(),
+                0,
+            ])
+            .unwrap(),
+            Column::try_from_slice::<UInt64Type>(&[1, 7]).unwrap(),
+            Column::from(Arc::new(BinaryArray::from(vec![&b"hi"[..], b""])) as Arc<dyn Array>),
+        ];
+        let table =
+            Table::<u64>::new(Arc::new(schema), columns, HashMap::new()).expect("invalid columns");
+        let column_types = [
+            ColumnType::Int64,
+            ColumnType::Utf8,
+            ColumnType::IpAddr,
+            ColumnType::Float64,
+            ColumnType::DateTime,
+            ColumnType::Enum,
+            ColumnType::Binary,
+        ];
+        let r_enum_maps: ReverseEnumMaps = [(5, [(1, vec!["tcp".to_string()])].into())].into();
+
+        let json = table
+            .to_json_rows(
+                &column_types,
+                &r_enum_maps,
+                UNKNOWN_ENUM_LABEL,
+                BinaryEncoding::Base64,
+            )
+            .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {
+                    "count": 1,
+                    "name": "a",
+                    "addr": "127.0.0.1",
+                    "score": 0.5,
+                    "time": "2019-09-22T06:10:11",
+                    "kind": "tcp",
+                    "raw": "aGk=",
+                },
+                {
+                    "count": null,
+                    "name": "b",
+                    "addr": "10.0.0.1",
+                    "score": null,
+                    "time": "1970-01-01T00:00:00",
+                    "kind": "_NO_MAP_",
+                    "raw": "",
+                },
+            ])
+        );
+        assert_eq!(
+            table
+                .to_json_rows(
+                    &column_types,
+                    &HashMap::new(),
+                    UNKNOWN_ENUM_LABEL,
+                    BinaryEncoding::default()
+                )
+                .unwrap()[1]["kind"],
+            7
+        );
+        assert_eq!(
+            table
+                .to_json_rows(
+                    &column_types,
+                    &r_enum_maps,
+                    "(unknown)",
+                    BinaryEncoding::default()
+                )
+                .unwrap()[1]["kind"],
+            "(unknown)"
+        );
+        assert!(table
+            .to_json_rows(
+                &column_types[1..],
+                &r_enum_maps,
+                UNKNOWN_ENUM_LABEL,
+                BinaryEncoding::default()
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn null_report() {
+        let c0: Arc<dyn Array> = Arc::new(Int64Array::from(vec![Some(1), None, Some(3), None]));
+        let c1: Arc<dyn Array> = Arc::new(StringArray::from(vec![
+            Some("a"),
+            Some("b"),
+            Some("c"),
+            Some("d"),
+        ]));
+        let mut c2 =
+            Column::from(Arc::new(Float64Array::from(vec![Some(1.0), None])) as Arc<dyn Array>);
+        let mut c2_tail =
+            Column::from(Arc::new(Float64Array::from(vec![None, Some(4.0)])) as Arc<dyn Array>);
+        c2.try_append(&mut c2_tail).unwrap();
+
+        let c0 = Column::from(c0);
+        assert_eq!(c0.null_rows(), vec![1, 3]);
+        assert_eq!(c2.null_rows(), vec![1, 2]);
+
+        let table = Table::<usize>::new(
+            Arc::new(Schema::empty()),
+            vec![c0, Column::from(c1), c2],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        assert_eq!(table.null_report(), vec![(0, 2), (1, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn unknown_enum_label() {
+        let column = Column::try_from_slice::<UInt64Type>(&[7, 9, 9, 7, 9]).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![column], HashMap::new())
+            .expect("invalid columns");
+        let r_enum_maps: ReverseEnumMaps =
+            vec![(0, vec![(7, vec!["tcp".to_string()])].into_iter().collect())]
+                .into_iter()
+                .collect();
+        let column_types = [ColumnType::Enum];
+        let labels = |stats: &ColumnStatistics| {
+            stats
+                .n_largest_count
+                .top_n()
+                .iter()
+                .map(|e| (e.value.clone(), e.count))
+                .collect::<Vec<_>>()
+        };
+
+        let stats = table
+            .lazy_statistics(&[0, 1, 2, 3, 4], &column_types, &r_enum_maps, &[], &[10], 2)
+            .unwrap();
+        assert_eq!(
+            labels(stats.get(0).unwrap()),
+            vec![
+                (Element::Enum("_NO_MAP_".to_string()), 3),
+                (Element::Enum("tcp".to_string()), 2)
+            ]
+        );
+
+        let stats = table
+            .lazy_statistics(&[0, 1, 2, 3, 4], &column_types, &r_enum_maps, &[], &[10], 2)
+            .unwrap()
+            .with_unknown_enum_label("(unknown)");
+        let stats = stats.get(0).unwrap();
+        assert_eq!(
+            labels(stats),
+            vec![
+                (Element::Enum("(unknown)".to_string()), 3),
+                (Element::Enum("tcp".to_string()), 2)
+            ]
+        );
+        assert_eq!(
+            stats.n_largest_count.mode(),
+            Some(&Element::Enum("(unknown)".to_string()))
+        );
+
+        let column_types = Arc::new(column_types.to_vec());
+        let stats = table
+            .statistics(
+                &[0, 1, 2, 3, 4],
+                &column_types,
+                &r_enum_maps,
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10]),
+                2,
+            )
+            .unwrap();
+        assert_eq!(
+            labels(&stats[0])[0],
+            (Element::Enum("_NO_MAP_".to_string()), 3)
+        );
+        let stats = table
+            .statistics_with_unknown_enum_label(
+                &[0, 1, 2, 3, 4],
+                &column_types,
+                &r_enum_maps,
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10]),
+                2,
+                "(unknown)",
+            )
+            .unwrap();
+        assert_eq!(
+            labels(&stats[0]),
+            vec![
+                (Element::Enum("(unknown)".to_string()), 3),
+                (Element::Enum("tcp".to_string()), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn column_dictionary_decode() {
+        let mut column = Column::try_from_slice_with_nulls::<UInt64Type>(
+            &[7, 0, 9, 8],
+            &[true, false, true, true],
+        )
+        .unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<UInt64Type>(&[6]).unwrap())
+            .unwrap();
+        let reverse_map: HashMap<u64, Vec<String>> = [
+            (7, vec!["tcp".to_string()]),
+            (8, vec![]),
+            (9, vec!["icmp".to_string(), "ping".to_string()]),
+        ]
+        .into_iter()
+        .collect();
+        let labels = |joiner| {
+            let decoded = column.dictionary_decode(&reverse_map, joiner, "?").unwrap();
+            decoded
+                .iter::<StringArray>()
+                .unwrap()
+                .map(|label| label.map(str::to_string))
+                .collect::<Vec<_>>()
+        };
+        let expected = |third: &str| {
+            vec![
+                Some("tcp".to_string()),
+                None,
+                Some(third.to_string()),
+                Some("?".to_string()),
+                Some("?".to_string()),
+            ]
+        };
+        assert_eq!(labels(None), expected("icmp"));
+        assert_eq!(labels(Some("|")), expected("icmp|ping"));
+
+        let ints = Column::try_from_slice::<Int64Type>(&[7]).unwrap();
+        assert_eq!(
+            ints.dictionary_decode(&reverse_map, None, "?"),
+            Err(TypeError())
+        );
+    }
+
+    #[test]
+    fn enum_record_batch_round_trip() {
+        let schema = Schema::new(vec![
+            Field::new("count", DataType::Int64, false),
+            Field::new("kind", DataType::UInt64, false),
+        ]);
+        let c0 = Column::try_from_slice::<Int64Type>(&[1, 2, 3, 4]).unwrap();
+        let mut c1 = Column::try_from_slice::<UInt64Type>(&[7, 9]).unwrap();
+        c1.try_append(&mut Column::try_from_slice::<UInt64Type>(&[7, 8]).unwrap())
+            .unwrap();
+        let table = Table::<usize>::new(Arc::new(schema), vec![c0, c1], HashMap::new())
+            .expect("invalid columns");
+        let r_enum_maps: ReverseEnumMaps = vec![(
+            1,
+            vec![
+                (7, vec!["tcp".to_string()]),
+                (8, vec!["udp".to_string()]),
+                (9, vec!["icmp".to_string()]),
+            ]
+            .into_iter()
+            .collect(),
+        )]
+        .into_iter()
+        .collect();
+
+        let column_types = [ColumnType::Int64, ColumnType::Enum];
+        let batch = table
+            .to_record_batch(&column_types, Some(&r_enum_maps))
+            .expect("valid table");
+        assert_eq!(batch.num_rows(), 4);
+        assert_eq!(batch.schema().field(1).name(), "kind");
+        assert_eq!(
+            batch.column(1).data_type(),
+            &DataType::Dictionary(Box::new(DataType::UInt64), Box::new(DataType::Utf8))
+        );
+
+        let dictionary = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<DictionaryArray<UInt64Type>>()
+            .expect("dictionary array");
+        let (column, reverse_map) = Column::try_from_dictionary(dictionary).unwrap();
+        let labels: Vec<_> = column
+            .primitive_iter::<UInt64Type>(&[0, 1, 2, 3])
+            .unwrap()
+            .map(|id| reverse_map[&id][0].clone())
+            .collect();
+        assert_eq!(labels, vec!["tcp", "icmp", "tcp", "udp"]);
+        assert_eq!(Column::from(batch.column(0).clone()), table.columns[0]);
+
+        let batch = table
+            .to_record_batch(&column_types, None)
+            .expect("valid table");
+        assert_eq!(batch.column(1).data_type(), &DataType::UInt64);
+    }
+
+    #[test]
+    fn dictionary_round_trip() {
+        let column = Column::from(Arc::new(UInt64Array::from(vec![
+            Some(7),
+            Some(3),
+            None,
+            Some(7),
+            Some(12),
+        ])) as Arc<dyn Array>);
+        let reverse_map: HashMap<u64, Vec<String>> =
+            vec![(7, vec!["tcp".to_string()]), (12, vec!["udp".to_string()])]
+                .into_iter()
+                .collect();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![column], HashMap::new())
+            .expect("invalid columns");
+        let r_enum_maps: ReverseEnumMaps = vec![(0, reverse_map.clone())].into_iter().collect();
+        let batch = table
+            .to_record_batch(&[ColumnType::Enum], Some(&r_enum_maps))
+            .expect("valid table");
+        let dictionary = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<DictionaryArray<UInt64Type>>()
+            .expect("dictionary array");
+        let (decoded, decoded_map) = Column::try_from_dictionary(dictionary).unwrap();
+        assert_eq!(decoded, table.columns[0]);
+        assert_eq!(decoded_map, reverse_map);
+
+        let empty = Column::try_from_slice::<UInt64Type>(&[]).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![empty], HashMap::new())
+            .expect("invalid columns");
+        let empty_batch = table
+            .to_record_batch(&[ColumnType::Enum], Some(&r_enum_maps))
+            .expect("valid table");
+        assert_eq!(empty_batch.num_rows(), 0);
+        assert_eq!(empty_batch.schema(), batch.schema());
+        let dictionary = empty_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<DictionaryArray<UInt64Type>>()
+            .expect("dictionary array");
+        let (decoded, decoded_map) = Column::try_from_dictionary(dictionary).unwrap();
+        assert_eq!(decoded.len(), 0);
+        assert!(decoded_map.is_empty());
+
+        let empty_batch = table
+            .to_record_batch(&[ColumnType::Enum], None)
+            .expect("valid table");
+        assert_eq!(empty_batch.column(0).data_type(), &DataType::UInt64);
+    }
+
+    #[test]
+    fn column_contains() {
+        let mut c0 = Column::try_from_slice::<Int64Type>(&[1, 3, 5]).unwrap();
+        c0.try_append(&mut Column::from(
+            Arc::new(Int64Array::from(vec![None, Some(7)])) as Arc<dyn Array>,
+        ))
+        .unwrap();
+        assert!(c0.contains_primitive::<Int64Type>(7));
+        assert!(!c0.contains_primitive::<Int64Type>(0));
+        assert!(!c0.contains_primitive::<UInt32Type>(1));
+
+        let c1 = Column::from(
+            Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])) as Arc<dyn Array>,
+        );
+        assert!(c1.contains_string("c"));
+        assert!(!c1.contains_string("b"));
+        assert!(!c1.contains_binary(b"c"));
+
+        let c2 = Column::from(
+            Arc::new(BinaryArray::from(vec![&b"\x00\x01"[..], b"x"])) as Arc<dyn Array>
+        );
+        assert!(c2.contains_binary(b"\x00\x01"));
+        assert!(!c2.contains_binary(b"\x00"));
+    }
+
+    #[test]
+    fn compare_scalar() {
+        let mut column =
+            Column::try_from_slice_with_nulls::<Int64Type>(&[1, 5, 0], &[true, true, false])
+                .unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[3, 7]).unwrap())
+            .unwrap();
+        let compare = |op| {
+            column
+                .compare_scalar_primitive::<Int64Type>(op, 3)
+                .unwrap()
+                .iter::<arrow::array::BooleanArray>()
+                .unwrap()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            compare(CompareOp::Gt),
+            vec![Some(false), Some(true), None, Some(false), Some(true)]
+        );
+        assert_eq!(
+            compare(CompareOp::Eq),
+            vec![Some(false), Some(false), None, Some(true), Some(false)]
+        );
+        assert_eq!(
+            compare(CompareOp::LtEq),
+            vec![Some(true), Some(false), None, Some(true), Some(false)]
+        );
+        assert_eq!(
+            column.compare_scalar_primitive::<UInt32Type>(CompareOp::Eq, 3),
+            Err(TypeError())
+        );
+    }
+
+    #[test]
+    fn n_extreme_values() {
+        let mut c = Column::from(Arc::new(Float64Array::from(vec![
+            Some(2.5),
+            None,
+            Some(9.0),
+            Some(-1.0),
+        ])) as Arc<dyn Array>);
+        c.try_append(&mut Column::try_from_slice::<Float64Type>(&[9.0, 4.0]).unwrap())
+            .unwrap();
+        let rows = [0_usize, 1, 2, 3, 4, 5];
+
+        assert_eq!(
+            c.n_largest_values::<Float64Type>(&rows, 3).unwrap(),
+            vec![(2, 9.0), (4, 9.0), (5, 4.0)]
+        );
+        assert_eq!(
+            c.n_smallest_values::<Float64Type>(&rows, 2).unwrap(),
+            vec![(3, -1.0), (0, 2.5)]
+        );
+        assert_eq!(
+            c.n_largest_values::<Float64Type>(&[1, 3], 3).unwrap(),
+            vec![(3, -1.0)]
+        );
+        assert_eq!(c.n_largest_values::<Int64Type>(&rows, 3), Err(TypeError()));
+    }
+
+    #[test]
+    fn table_from_csv_paths() {
+        let dir = std::env::temp_dir();
+        let paths = [
+            dir.join(format!("structured-shard-{}-0.csv", std::process::id())),
+            dir.join(format!("structured-shard-{}-1.csv", std::process::id())),
+        ];
+        std::fs::write(&paths[0], b"11,a\n12,\"b\nc\"\n13,d\n").unwrap();
+        std::fs::write(&paths[1], b"14,e\r\n15,f\r\n").unwrap();
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::UInt64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let parsers = [FieldParser::uint64(), FieldParser::Utf8];
+        let read = |schema: &Arc<Schema>, event_id_column| {
+            Table::<u64>::from_csv_paths::<UInt64Type, _>(
+                schema.clone(),
+                &paths,
+                &parsers,
+                2,
+                event_id_column,
+            )
+        };
+        let table = read(&schema, 0);
+        let wrong_event_ids = read(&schema, 1);
+        let narrow_schema = Arc::new(Schema::new(vec![Field::new("id", DataType::UInt64, false)]));
+        let too_few_fields = read(&narrow_schema, 0);
+        let int_schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let wrong_type = read(&int_schema, 0);
+        for path in &paths {
+            std::fs::remove_file(path).unwrap();
+        }
+        let table = table.expect("valid shards");
+
+        assert_eq!(table.num_rows(), 5);
+        let rows = [0_usize, 1, 2, 3, 4];
+        let ids: Vec<_> = table.columns[0]
+            .primitive_iter::<UInt64Type>(&rows)
+            .unwrap()
+            .collect();
+        assert_eq!(ids, vec![11, 12, 13, 14, 15]);
+        let names: Vec<_> = table.columns[1].string_iter(&rows).unwrap().collect();
+        assert_eq!(names, vec!["a", "b\nc", "d", "e", "f"]);
+        assert_eq!(table.event_index(&14), Some(&3));
+
+        assert!(matches!(
+            wrong_event_ids,
+            Err(TableError::UnexpectedType(1))
+        ));
+        assert!(matches!(
+            too_few_fields,
+            Err(TableError::NumColumnsMismatch {
+                expected: 1,
+                found: 2
+            })
+        ));
+        assert!(matches!(wrong_type, Err(TableError::UnexpectedType(0))));
+        assert!(matches!(read(&schema, 0), Err(TableError::Io(_))));
+    }
+
+    #[test]
+    fn typed_columns() {
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+            Field::new("len", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("flag", DataType::Boolean, false),
+        ]);
+        let c0 = Column::try_from_slice::<Int64Type>(&[10, 20]).unwrap();
+        let c1 = Column::try_from_slice::<Int64Type>(&[3, 4]).unwrap();
+        let c2 = Column::from(Arc::new(StringArray::from(vec!["a", "b"])) as Arc<dyn Array>);
+        let c3 = Column::from(
+            Arc::new(arrow::array::BooleanArray::from(vec![true, false])) as Arc<dyn Array>,
+        );
+        let table = Table::<usize>::new(Arc::new(schema), vec![c0, c1, c2, c3], HashMap::new())
+            .expect("invalid columns");
+
+        let Some(TypedColumn::DateTime(ts)) = table.typed_column(0) else {
+            panic!("timestamp column expected");
+        };
+        assert_eq!(ts.primitive_try_get::<Int64Type>(1), Ok(Some(20)));
+        assert!(matches!(table.typed_column(1), Some(TypedColumn::Int64(_))));
+        match table.typed_column(2) {
+            Some(TypedColumn::Utf8(c)) => assert_eq!(c.string_try_get(0), Ok(Some("a"))),
+            other => panic!("string column expected, got {other:?}"),
+        }
+        assert_eq!(table.typed_column(3), None);
+        assert_eq!(table.typed_column(4), None);
+    }
+
+    #[test]
+    fn column_index() {
+        let schema = Schema::new(vec![
+            Field::new("len", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("len", DataType::Boolean, false),
+        ]);
+        let c0 = Column::try_from_slice::<Int64Type>(&[3, 4]).unwrap();
+        let c1 = Column::from(Arc::new(StringArray::from(vec!["a", "b"])) as Arc<dyn Array>);
+        let c2 = Column::from(
+            Arc::new(arrow::array::BooleanArray::from(vec![true, false])) as Arc<dyn Array>,
+        );
+        let table = Table::<usize>::new(Arc::new(schema), vec![c0, c1, c2], HashMap::new())
+            .expect("invalid columns");
+
+        assert_eq!(table.column_index("name"), Some(1));
+        assert_eq!(table.column_index("len"), Some(0));
+        assert_eq!(table.column_index("flag"), None);
+        let Some(name) = table.column_by_name("name") else {
+            panic!("column `name` expected");
+        };
+        assert_eq!(name.string_try_get(1), Ok(Some("b")));
+        assert!(table.column_by_name("").is_none());
+    }
+
+    #[test]
+    fn rows() {
+        let mut c0 =
+            Column::try_from_slice_with_nulls::<Int64Type>(&[1, 0], &[true, false]).unwrap();
+        c0.try_append(&mut Column::try_from_slice::<Int64Type>(&[3]).unwrap())
+            .unwrap();
+        let mut c1 = Column::from(Arc::new(StringArray::from(vec!["a"])) as Arc<dyn Array>);
+        c1.try_append(&mut Column::from(
+            Arc::new(StringArray::from(vec!["b", "c"])) as Arc<dyn Array>,
+        ))
+        .unwrap();
+        let c2 = Column::try_from_slice::<UInt32Type>(&[1, 2, 3]).unwrap();
+        let c3 = Column::try_from_slice::<Int64Type>(&[0, 1_000_000_000, 60_000_000_000]).unwrap();
+        let table = Table::<usize>::new(
+            Arc::new(Schema::empty()),
+            vec![c0, c1, c2, c3],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        let column_types = [
+            ColumnType::Int64,
+            ColumnType::Utf8,
+            ColumnType::IpAddr,
+            ColumnType::DateTime,
+        ];
+
+        let rows: Vec<_> = table.rows(&column_types).unwrap().collect();
+        let time = |min, sec| {
+            NaiveDate::from_ymd_opt(1970, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, min, sec)
+                .map(Element::DateTime)
+        };
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(Element::Int(1)),
+                    Some(Element::Text("a".to_string())),
+                    Some(Element::IpAddr(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 1)))),
+                    time(0, 0),
+                ],
+                vec![
+                    None,
+                    Some(Element::Text("b".to_string())),
+                    Some(Element::IpAddr(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 2)))),
+                    time(0, 1),
+                ],
+                vec![
+                    Some(Element::Int(3)),
+                    Some(Element::Text("c".to_string())),
+                    Some(Element::IpAddr(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 3)))),
+                    time(1, 0),
+                ],
+            ]
+        );
+
+        assert!(table.rows(&column_types[..3]).is_err());
+        let mut wrong_types = column_types;
+        wrong_types[1] = ColumnType::Binary;
+        assert!(table.rows(&wrong_types).is_err());
+    }
+
+    #[test]
+    fn coalesce() {
+        let primary = Column::try_from_slice_with_nulls::<Int64Type>(
+            &[1, 0, 3, 0],
+            &[true, false, true, false],
+        )
+        .unwrap();
+        let mut backup = Column::try_from_slice::<Int64Type>(&[10, 20]).unwrap();
+        backup
+            .try_append(
+                &mut Column::try_from_slice_with_nulls::<Int64Type>(&[30, 0], &[true, false])
+                    .unwrap(),
+            )
+            .unwrap();
+        let other =
+            Column::from(Arc::new(StringArray::from(vec!["a", "b", "c", "d"])) as Arc<dyn Array>);
+        let table = Table::<usize>::new(
+            Arc::new(Schema::empty()),
+            vec![primary, backup, other],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+
+        let merged = table.coalesce(0, 1).unwrap();
+        assert_eq!(
+            merged.iter::<Int64Array>().unwrap().collect::<Vec<_>>(),
+            vec![Some(1), Some(20), Some(3), None]
+        );
+        let merged = table.coalesce(1, 0).unwrap();
+        assert_eq!(
+            merged.iter::<Int64Array>().unwrap().collect::<Vec<_>>(),
+            vec![Some(10), Some(20), Some(30), None]
+        );
+        assert!(matches!(
+            table.coalesce(0, 2),
+            Err(TableError::TypeMismatch(0, 2))
+        ));
+        assert!(matches!(
+            table.coalesce(0, 3),
+            Err(TableError::ColumnIndexOutOfRange(3))
+        ));
+    }
+
+    #[test]
+    fn map_column() {
+        let schema = Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            Field::new("millis", DataType::Int64, true),
+        ]);
+        let c0 = Column::from(Arc::new(StringArray::from(vec!["a", "b", "c"])) as Arc<dyn Array>);
+        let mut c1 =
+            Column::try_from_slice_with_nulls::<Int64Type>(&[1500, 0], &[true, false]).unwrap();
+        c1.try_append(&mut Column::try_from_slice::<Int64Type>(&[250]).unwrap())
+            .unwrap();
+        let mut table = Table::<usize>::new(Arc::new(schema), vec![c0, c1], HashMap::new())
+            .expect("invalid columns");
+
+        #[allow(clippy::cast_precision_loss)]
+        let to_secs = |v: i64| v as f64 / 1000.0;
+        let field = Field::new("secs", DataType::Float64, true);
+        assert!(matches!(
+            table.map_column::<Int64Type, Float64Type, _>(0, field.clone(), to_secs),
+            Err(TableError::UnexpectedType(0))
+        ));
+        assert!(matches!(
+            table.map_column::<Int64Type, Float64Type, _>(
+                1,
+                Field::new("secs", DataType::Int64, true),
+                to_secs
+            ),
+            Err(TableError::UnexpectedType(1))
+        ));
+        assert!(matches!(
+            table.map_column::<Int64Type, Float64Type, _>(2, field.clone(), to_secs),
+            Err(TableError::ColumnIndexOutOfRange(2))
+        ));
+        assert_eq!(table.schema().field(1).name(), "millis");
+
+        table
+            .map_column::<Int64Type, Float64Type, _>(1, field.clone(), to_secs)
+            .unwrap();
+        assert_eq!(table.schema().field(1).name(), "secs");
+        assert_eq!(table.schema().field(1).data_type(), &DataType::Float64);
+        assert_eq!(
+            table
+                .column(1)
+                .unwrap()
+                .iter::<Float64Array>()
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![Some(1.5), None, Some(0.25)]
+        );
+        assert!(matches!(
+            table.typed_column(1),
+            Some(TypedColumn::Float64(_))
+        ));
+
+        // The field is checked even if the column has no array.
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+            Field::new("ratio", DataType::Float64, false),
+        ]);
+        let mut table = Table::<usize>::new(
+            Arc::new(schema),
+            vec![Column::default(), Column::default()],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        assert!(matches!(
+            table.map_column::<Int64Type, Float64Type, _>(1, field.clone(), to_secs),
+            Err(TableError::UnexpectedType(1))
+        ));
+        let ts = Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false);
+        table
+            .map_column::<Int64Type, Int64Type, _>(0, ts, |v| v + 1)
+            .unwrap();
+        table
+            .map_column::<Int64Type, Float64Type, _>(0, field, to_secs)
+            .unwrap();
+        assert_eq!(table.schema().field(0).data_type(), &DataType::Float64);
+    }
+
+    #[test]
+    fn rebuild_event_ids() {
+        let ids = Column::try_from_slice::<UInt64Type>(&[12, 10, 11]).unwrap();
+        let values = Column::try_from_slice::<Int64Type>(&[3, 1, 2]).unwrap();
+        let event_ids: HashMap<u64, usize> = [(12, 0), (10, 1), (11, 2)].into_iter().collect();
+        let table = Table::new(Arc::new(Schema::empty()), vec![ids, values], event_ids)
+            .expect("invalid columns");
+
+        // Sort the rows by the second column.
+        let order = arrow::compute::sort_to_indices(
+            table.columns[1].concat().unwrap().unwrap().as_ref(),
+            None,
+            None,
+        )
+        .unwrap();
+        let columns = table
+            .columns
+            .iter()
+            .map(|c| {
+                let array = c.concat().unwrap().unwrap();
+                Column::from(arrow::compute::take(array.as_ref(), &order, None).unwrap())
+            })
+            .collect();
+        let mut sorted = Table::new(table.schema().clone(), columns, table.event_ids().clone())
+            .expect("invalid columns");
+        assert_eq!(sorted.event_index(&10), Some(&1));
+
+        sorted.rebuild_event_ids::<UInt64Type>(0).unwrap();
+        assert_eq!(
+            sorted.event_ids(),
+            &[(10, 0), (11, 1), (12, 2)].into_iter().collect()
+        );
+        assert_eq!(
+            sorted.event_indices(&[10, 11, 12, 13]),
+            [Some(0), Some(1), Some(2), None]
+        );
+        assert!(matches!(
+            sorted.rebuild_event_ids::<UInt64Type>(1),
+            Err(TableError::UnexpectedType(1))
+        ));
+        assert!(matches!(
+            sorted.rebuild_event_ids::<UInt64Type>(2),
+            Err(TableError::ColumnIndexOutOfRange(2))
+        ));
+        assert_eq!(sorted.event_index(&12), Some(&2));
+    }
+
+    #[test]
+    fn event_indices() {
+        let column = Column::try_from_slice::<Int64Type>(&[1, 2, 3]).unwrap();
+        let event_ids: HashMap<u64, usize> = [(10, 0), (11, 1), (12, 2)].into_iter().collect();
+        let table = Table::new(Arc::new(Schema::empty()), vec![column], event_ids)
+            .expect("invalid columns");
+        assert_eq!(
+            table.event_indices(&[12, 99, 10, 10]),
+            vec![Some(2), None, Some(0), Some(0)]
+        );
+        assert!(table.event_indices(&[]).is_empty());
+    }
+
+    #[test]
+    fn correlation() {
+        let columns = vec![
+            Column::try_from_slice::<Int64Type>(&[0, 1, 2, 3, 4, 5]).unwrap(),
+            Column::try_from_slice::<Float64Type>(&[3.0, 1.0, -1.0, -3.0, -5.0, -7.0]).unwrap(),
+            Column::try_from_slice_with_nulls::<Float64Type>(
+                &[99.0, 2.0, 4.0, 5.0, 4.0, 5.0],
+                &[false, true, true, true, true, true],
+            )
+            .unwrap(),
+            Column::try_from_slice::<Float64Type>(&[2.0; 6]).unwrap(),
+            Column::from(Arc::new(StringArray::from(vec!["a"; 6])) as Arc<dyn Array>),
+            Column::try_from_slice::<Float32Type>(&[3.0, 1.0, -1.0, -3.0, -5.0, -7.0]).unwrap(),
+            Column::try_from_slice::<Int32Type>(&[0, 1, 2, 3, 4, 5]).unwrap(),
+        ];
+        let table = Table::<u64>::new(Arc::new(Schema::empty()), columns, HashMap::new())
+            .expect("invalid columns");
+        let all: Vec<usize> = (0..6).collect();
+
+        let r = table.correlation(0, 1, &all).unwrap();
+        assert!((r + 1.0).abs() < 1e-12);
+        assert!((table.correlation(1, 0, &all).unwrap() - r).abs() < 1e-12);
+        assert!((table.correlation(0, 0, &all).unwrap() - 1.0).abs() < 1e-12);
+
+        // Row 0 is skipped because of the null.
+        let r = table.correlation(0, 2, &all).unwrap();
+        assert!((r - 0.6_f64.sqrt()).abs() < 1e-12);
+
+        assert_eq!(table.correlation(0, 3, &all), None);
+        assert_eq!(table.correlation(0, 1, &[4]), None);
+        assert_eq!(table.correlation(0, 4, &all), None);
+        assert_eq!(table.correlation(0, 7, &all), None);
+
+        // Narrow numeric types
+        let r = table.correlation(5, 6, &all).unwrap();
+        assert!((r + 1.0).abs() < 1e-12);
+        let r = table.correlation(0, 6, &all).unwrap();
+        assert!((r - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rows_matching() {
+        let addresses: Vec<u32> = [
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 1, 2),
+            Ipv4Addr::new(192, 168, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 3),
+            Ipv4Addr::new(10, 0, 0, 4),
+        ]
+        .into_iter()
+        .map(u32::from)
+        .collect();
+        let columns = vec![
+            Column::try_from_slice::<UInt32Type>(&addresses).unwrap(),
+            Column::try_from_slice_with_nulls::<Int64Type>(
+                &[404, 500, 503, 200, 0],
+                &[true, true, true, true, false],
+            )
+            .unwrap(),
+        ];
+        let table = Table::<u64>::new(Arc::new(Schema::empty()), columns, HashMap::new())
+            .expect("invalid columns");
+        let in_subnet = RowFilter::Column(0, Predicate::InSubnet(Ipv4Addr::new(10, 0, 0, 0), 24));
+        let is_error = RowFilter::Column(1, Predicate::Int64(CompareOp::GtEq, 400));
+
+        assert_eq!(
+            table
+                .rows_matching(&RowFilter::And(vec![in_subnet.clone(), is_error.clone()]))
+                .unwrap(),
+            [0]
+        );
+        assert_eq!(
+            table
+                .rows_matching(&RowFilter::Or(vec![in_subnet.clone(), is_error.clone()]))
+                .unwrap(),
+            [0, 1, 2, 3, 4]
+        );
+        assert_eq!(
+            table
+                .rows_matching(&RowFilter::Not(Box::new(is_error)))
+                .unwrap(),
+            [3, 4]
+        );
+        assert_eq!(
+            table.rows_matching(&RowFilter::And(Vec::new())).unwrap(),
+            [0, 1, 2, 3, 4]
+        );
+        assert!(matches!(
+            table.rows_matching(&RowFilter::Column(1, Predicate::UInt64(CompareOp::Eq, 1))),
+            Err(TableError::UnexpectedType(1))
+        ));
+        assert!(matches!(
+            table.rows_matching(&RowFilter::Column(2, Predicate::StringEq("a".to_string()))),
+            Err(TableError::ColumnIndexOutOfRange(2))
+        ));
+    }
+
+    #[test]
+    fn top_k_rows() {
+        let mut values = Column::try_from_slice::<Int64Type>(&[5, 9, 1, 9, 7]).unwrap();
+        values
+            .try_append(
+                &mut Column::try_from_slice_with_nulls::<Int64Type>(
+                    &[100, 3, 7],
+                    &[false, true, true],
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let table = Table::<u64>::new(Arc::new(Schema::empty()), vec![values], HashMap::new())
+            .expect("invalid columns");
+
+        assert_eq!(
+            table.top_k_rows::<Int64Type>(0, 3, true).unwrap(),
+            [1, 3, 4]
+        );
+        assert_eq!(
+            table.top_k_rows::<Int64Type>(0, 3, false).unwrap(),
+            [2, 6, 0]
+        );
+        assert_eq!(table.top_k_rows::<Int64Type>(0, 10, true).unwrap().len(), 7);
+        assert!(table
+            .top_k_rows::<Int64Type>(0, 0, true)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            table.top_k_rows::<Float64Type>(0, 3, true),
+            Err(TableError::UnexpectedType(0))
+        ));
+        assert!(matches!(
+            table.top_k_rows::<Int64Type>(1, 3, true),
+            Err(TableError::ColumnIndexOutOfRange(1))
+        ));
+    }
+
+    #[test]
+    fn sample_rows() {
+        let values: Vec<i64> = (0..1000).collect();
+        let column = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![column], HashMap::new())
+            .expect("invalid columns");
+
+        let sample = table.sample_rows(0.1, 42).unwrap();
+        assert_eq!(sample, table.sample_rows(0.1, 42).unwrap());
+        assert_ne!(sample, table.sample_rows(0.1, 43).unwrap());
+        assert!(sample.windows(2).all(|w| w[0] < w[1]));
+        assert!((50..150).contains(&sample.len()));
+
+        assert!(table.sample_rows(0.0, 42).unwrap().is_empty());
+        assert_eq!(table.sample_rows(1.0, 42).unwrap().len(), 1000);
+        assert!(matches!(
+            table.sample_rows(1.5, 42),
+            Err(TableError::InvalidFraction(_))
+        ));
+        assert!(table.sample_rows(f64::NAN, 42).is_err());
+    }
+
+    #[test]
+    fn partition() {
+        let mut column = Column::try_from_slice::<Int64Type>(&[0, 1, 2, 3, 4]).unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[5, 6, 7]).unwrap())
+            .unwrap();
+        let event_ids: HashMap<u64, usize> = (0..8).map(|row| (100 + row as u64, row)).collect();
+        let table = Table::new(Arc::new(Schema::empty()), vec![column], event_ids)
+            .expect("invalid columns");
+
+        let partitions = table.partition(3);
+        assert_eq!(
+            partitions.iter().map(Table::num_rows).collect::<Vec<_>>(),
+            vec![3, 3, 2]
+        );
+        let values = |t: &Table<u64>| {
+            t.column(0)
+                .unwrap()
+                .primitive_iter::<Int64Type>(&(0..t.num_rows()).collect::<Vec<_>>())
+                .unwrap()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(&partitions[0]), vec![0, 1, 2]);
+        assert_eq!(values(&partitions[1]), vec![3, 4, 5]);
+        assert_eq!(values(&partitions[2]), vec![6, 7]);
+        assert_eq!(partitions[0].event_index(&102), Some(&2));
+        assert_eq!(partitions[1].event_index(&104), Some(&1));
+        assert_eq!(partitions[1].event_index(&102), None);
+        assert_eq!(partitions[2].event_index(&107), Some(&1));
+
+        let partitions = table.partition(10);
+        assert_eq!(partitions.len(), 10);
+        assert_eq!(partitions[9].num_rows(), 0);
+        assert_eq!(
+            partitions[9].column(0).unwrap().data_type(),
+            Some(&DataType::Int64)
+        );
+        assert!(table.partition(0).is_empty());
+    }
+
+    #[test]
+    fn slice() {
+        let mut column = Column::try_from_slice::<Int64Type>(&[0, 1, 2]).unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[3, 4]).unwrap())
+            .unwrap();
+        column
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[5, 6, 7]).unwrap())
+            .unwrap();
+
+        let sliced = column.slice(2, 4);
+        assert_eq!(sliced.len(), 4);
+        assert_eq!(sliced.arrays.len(), 3);
+        assert_eq!(
+            sliced.to_vec_primitive::<Int64Type>().unwrap(),
+            [2, 3, 4, 5]
+        );
+        assert_eq!(sliced.primitive_try_get::<Int64Type>(3), Ok(Some(5)));
+        assert_eq!(column.slice(3, 2).arrays.len(), 1);
+        assert_eq!(
+            column.slice(6, 10).to_vec_primitive::<Int64Type>().unwrap(),
+            [6, 7]
+        );
+        for empty in [column.slice(4, 0), column.slice(9, 1)] {
+            assert_eq!(empty.len(), 0);
+            assert_eq!(empty.data_type(), Some(&DataType::Int64));
         }
+
+        let event_ids: HashMap<u64, usize> = (0..8).map(|row| (100 + row as u64, row)).collect();
+        let table = Table::new(Arc::new(Schema::empty()), vec![column], event_ids)
+            .expect("invalid columns");
+        let sliced = table.slice(5, 10);
+        assert_eq!(sliced.num_rows(), 3);
+        assert_eq!(sliced.event_index(&105), Some(&0));
+        assert_eq!(sliced.event_index(&107), Some(&2));
+        assert_eq!(sliced.event_index(&104), None);
     }
-}
 
-impl From<Arc<dyn Array>> for Column {
-    #[must_use]
-    fn from(array: Arc<dyn Array>) -> Self {
-        let len = array.len();
-        Self {
-            arrays: vec![array],
-            cumlen: vec![0, len],
-            len,
-        }
+    #[test]
+    fn typed_schema() {
+        let fields = vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+            Field::new("addr", DataType::UInt32, false),
+            Field::new("kind", DataType::UInt64, false),
+            Field::new("flag", DataType::Boolean, false),
+            Field::new("port", DataType::UInt16, false),
+            Field::new("ratio", DataType::Float64, true),
+        ];
+        let table = Table::<usize>::new(
+            Arc::new(Schema::new(fields.clone())),
+            Vec::new(),
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        assert_eq!(
+            table.typed_schema(),
+            vec![
+                (fields[0].clone(), Some(ColumnType::DateTime)),
+                (fields[1].clone(), Some(ColumnType::IpAddr)),
+                (fields[2].clone(), Some(ColumnType::Enum)),
+                (fields[3].clone(), Some(ColumnType::Boolean)),
+                (fields[4].clone(), None),
+                (fields[5].clone(), Some(ColumnType::Float64)),
+            ]
+        );
     }
-}
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct TypeError();
+    #[test]
+    fn remap() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, true),
+            ColumnType::Enum.annotate(Field::new("c", DataType::UInt64, false)),
+            Field::new("d", DataType::Float64, false),
+        ]);
+        let strings: Arc<dyn Array> = Arc::new(StringArray::from(vec!["x", "y"]));
+        let columns = vec![
+            Column::try_from_slice::<Int64Type>(&[1, 2]).unwrap(),
+            Column::from(strings),
+            Column::try_from_slice::<UInt64Type>(&[7, 8]).unwrap(),
+            Column::try_from_slice::<Float64Type>(&[0.5, 1.5]).unwrap(),
+        ];
+        let event_ids = [(10_usize, 0), (11, 1)].into_iter().collect();
+        let table = Table::new(Arc::new(schema), columns, event_ids).expect("invalid columns");
 
-pub struct PrimitiveIter<'a, 'b, T: ArrowPrimitiveType> {
-    column: &'a Column,
-    selected: slice::Iter<'b, usize>,
-    _t_marker: PhantomData<T>,
-}
+        let remapped = table
+            .remap(&[(3, "ratio"), (0, "count"), (2, "kind")])
+            .unwrap();
+        let fields = remapped.schema().fields();
+        assert_eq!(
+            fields
+                .iter()
+                .map(|f| (f.name().as_str(), f.data_type().clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("ratio", DataType::Float64),
+                ("count", DataType::Int64),
+                ("kind", DataType::UInt64)
+            ]
+        );
+        assert_eq!(ColumnType::of_field(&fields[2]), Some(ColumnType::Enum));
+        assert_eq!(remapped.num_columns(), 3);
+        assert_eq!(remapped.column(0), table.column(3));
+        assert_eq!(remapped.column(1), table.column(0));
+        assert_eq!(remapped.column(2), table.column(2));
+        assert_eq!(remapped.event_index(&11), Some(&1));
 
-impl<'a, 'b, T> PrimitiveIter<'a, 'b, T>
-where
-    T: ArrowPrimitiveType,
-{
-    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
-        Self {
-            column,
-            selected,
-            _t_marker: PhantomData,
-        }
+        assert!(matches!(
+            table.remap(&[(0, "a"), (4, "e")]),
+            Err(TableError::ColumnIndexOutOfRange(4))
+        ));
+
+        let projected = table.project(&[1, 3]).unwrap();
+        assert_eq!(
+            projected
+                .schema()
+                .fields()
+                .iter()
+                .map(|f| (f.name().as_str(), f.is_nullable()))
+                .collect::<Vec<_>>(),
+            [("b", true), ("d", false)]
+        );
+        assert_eq!(projected.column(0), table.column(1));
+        assert_eq!(projected.column(1), table.column(3));
+        assert_eq!(projected.event_index(&10), Some(&0));
+        assert!(matches!(
+            table.project(&[5]),
+            Err(TableError::ColumnIndexOutOfRange(5))
+        ));
     }
-}
 
-impl<T> Iterator for PrimitiveIter<'_, '_, T>
-where
-    T: ArrowPrimitiveType,
-{
-    type Item = T::Native;
+    #[test]
+    fn boolean_statistics() {
+        let flags: Arc<dyn Array> = Arc::new(BooleanArray::from(vec![
+            Some(true),
+            Some(false),
+            None,
+            Some(true),
+        ]));
+        let column = Column::from(flags);
+        let head: Arc<dyn Array> = Arc::new(BooleanArray::from(vec![Some(true), Some(false)]));
+        let tail: Arc<dyn Array> = Arc::new(BooleanArray::from(vec![None, Some(true)]));
+        let mut chunked = Column::from(head);
+        chunked.try_append(&mut Column::from(tail)).unwrap();
+        assert_eq!(column, chunked);
+        let other: Arc<dyn Array> = Arc::new(BooleanArray::from(vec![
+            Some(true),
+            Some(false),
+            None,
+            Some(false),
+        ]));
+        assert_ne!(column, Column::from(other));
+        assert_eq!(
+            column.element_at(0, ColumnType::Boolean),
+            Ok(Some(Element::Enum("true".to_string())))
+        );
+        assert_eq!(column.element_at(2, ColumnType::Boolean), Ok(None));
+        assert!(column.element_at(0, ColumnType::Int64).is_err());
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let selected = self.selected.next()?;
-        self.column
-            .primitive_try_get::<T>(*selected)
-            .unwrap_or_default()
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![column], HashMap::new())
+            .expect("invalid columns");
+        let stats = table
+            .statistics(
+                &[0, 1, 2, 3],
+                &Arc::new(vec![ColumnType::Boolean]),
+                &HashMap::new(),
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10]),
+                2,
+            )
+            .unwrap();
+        let n_largest_count = &stats[0].n_largest_count;
+        assert_eq!(n_largest_count.number_of_elements(), 2);
+        assert_eq!(
+            n_largest_count.mode(),
+            Some(&Element::Enum("true".to_string()))
+        );
+        assert_eq!(n_largest_count.top_n()[0].count, 2);
+        assert_eq!(n_largest_count.top_n()[1].count, 1);
+        assert_eq!(stats[0].description.count(), 4);
     }
-}
 
-pub struct BinaryIter<'a, 'b> {
-    column: &'a Column,
-    selected: slice::Iter<'b, usize>,
-}
+    #[test]
+    fn narrow_int_statistics() {
+        let values = [3_i64, -1, 3, 7];
+        let wide = Column::try_from_slice::<Int64Type>(&values).unwrap();
+        let narrow: Arc<dyn Array> = Arc::new(Int16Array::from_iter_values(
+            values.iter().map(|&v| i16::try_from(v).unwrap()),
+        ));
+        let schema = Schema::new(vec![
+            Field::new("wide", DataType::Int64, false),
+            Field::new("narrow", DataType::Int16, false),
+        ]);
+        let table = Table::<usize>::new(
+            Arc::new(schema),
+            vec![wide, Column::from(narrow)],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        let column_types = table.column_types().unwrap();
+        assert_eq!(column_types.as_slice(), [ColumnType::Int64; 2]);
 
-impl<'a, 'b> BinaryIter<'a, 'b> {
-    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
-        Self { column, selected }
+        let stats = table
+            .statistics(
+                &[0, 1, 2, 3],
+                &column_types,
+                &HashMap::new(),
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10; 2]),
+                2,
+            )
+            .unwrap();
+        assert_eq!(stats[1].description, stats[0].description);
+        assert_eq!(stats[1].description.min(), Some(&Element::Int(-1)));
+        assert_eq!(stats[1].n_largest_count.number_of_elements(), 3);
+        assert_eq!(stats[1].n_largest_count.mode(), Some(&Element::Int(3)));
     }
-}
 
-impl<'a> Iterator for BinaryIter<'a, '_> {
-    type Item = &'a [u8];
+    #[test]
+    fn float32_statistics() {
+        let values = [0.5_f32, -1.25, 0.5, 8.0];
+        let wide = Column::try_from_slice::<Float64Type>(
+            &values.iter().map(|&v| f64::from(v)).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let narrow = Column::try_from_slice::<Float32Type>(&values).unwrap();
+        let schema = Schema::new(vec![
+            Field::new("wide", DataType::Float64, false),
+            Field::new("narrow", DataType::Float32, false),
+        ]);
+        let table = Table::<usize>::new(Arc::new(schema), vec![wide, narrow], HashMap::new())
+            .expect("invalid columns");
+        let column_types = table.column_types().unwrap();
+        assert_eq!(column_types.as_slice(), [ColumnType::Float64; 2]);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let selected = self.selected.next()?;
-        self.column.binary_try_get(*selected).unwrap_or_default()
+        let stats = table
+            .statistics(
+                &[0, 1, 2, 3],
+                &column_types,
+                &HashMap::new(),
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10; 2]),
+                2,
+            )
+            .unwrap();
+        assert_eq!(stats[1].description, stats[0].description);
+        assert_eq!(stats[1].description.max(), Some(&Element::Float(8.0)));
+        assert_eq!(stats[1].n_largest_count.number_of_elements(), 3);
     }
-}
 
-pub struct StringIter<'a, 'b> {
-    column: &'a Column,
-    selected: slice::Iter<'b, usize>,
-}
+    #[test]
+    fn self_described_column_types() {
+        let fields = vec![
+            ColumnType::Enum.annotate(Field::new("kind", DataType::UInt64, false)),
+            ColumnType::UInt64.annotate(Field::new("port", DataType::UInt64, false)),
+            Field::new("addr", DataType::UInt32, false),
+        ];
+        assert_eq!(
+            fields[1].metadata().get(ColumnType::METADATA_KEY).unwrap(),
+            "uint64"
+        );
+        let columns = vec![
+            Column::try_from_slice::<UInt64Type>(&[1, 2]).unwrap(),
+            Column::try_from_slice::<UInt64Type>(&[80, 443]).unwrap(),
+            Column::try_from_slice::<UInt32Type>(&[1, 2]).unwrap(),
+        ];
+        let table = Table::<usize>::new(Arc::new(Schema::new(fields)), columns, HashMap::new())
+            .expect("invalid columns");
+        let column_types = table.column_types().unwrap();
+        assert_eq!(
+            *column_types,
+            [ColumnType::Enum, ColumnType::UInt64, ColumnType::IpAddr]
+        );
+        let stats = table
+            .statistics(
+                &[0, 1],
+                &column_types,
+                &HashMap::new(),
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10; 3]),
+                2,
+            )
+            .unwrap();
+        assert_eq!(stats.len(), 3);
 
-impl<'a, 'b> StringIter<'a, 'b> {
-    fn new(column: &'a Column, selected: slice::Iter<'b, usize>) -> Self {
-        Self { column, selected }
+        let count = Field::new("count", DataType::UInt16, false);
+        assert_eq!(ColumnType::of_field(&count), None);
+        let mut metadata = HashMap::new();
+        metadata.insert(ColumnType::METADATA_KEY.to_string(), "bogus".to_string());
+        let kind = Field::new("kind", DataType::UInt64, false).with_metadata(metadata);
+        assert_eq!(ColumnType::of_field(&kind), Some(ColumnType::Enum));
+
+        let table = Table::<usize>::new(
+            Arc::new(Schema::empty()),
+            vec![Column::try_from_slice::<UInt64Type>(&[1]).unwrap()],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        assert!(table.column_types().is_none());
     }
-}
 
-impl<'a> Iterator for StringIter<'a, '_> {
-    type Item = &'a str;
+    #[test]
+    fn schema_equality() {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Utf8, false),
+        ]);
+        let renamed = Schema::new(vec![
+            Field::new("x", DataType::Int64, true),
+            Field::new("y", DataType::Utf8, false),
+        ]);
+        let retyped = Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Binary, false),
+        ]);
+        assert!(schema.types_equal(&renamed));
+        assert!(!schema.fields_equal(&renamed));
+        assert!(!schema.types_equal(&retyped));
+        assert!(schema.fields_equal(&schema));
+        assert!(!schema.types_equal(&Schema::new(vec![Field::new("a", DataType::Int64, false)])));
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let selected = self.selected.next()?;
-        self.column.string_try_get(*selected).unwrap_or_default()
+        let table = |schema: &Schema, values: &[i64]| {
+            let c0 = Column::try_from_slice::<Int64Type>(values).unwrap();
+            let c1 = Column::from(Arc::new(StringArray::from(
+                values.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            )) as Arc<dyn Array>);
+            Table::<usize>::new(Arc::new(schema.clone()), vec![c0, c1], HashMap::new())
+                .expect("invalid columns")
+        };
+        let mut base = table(&schema, &[1, 2]);
+        assert!(matches!(
+            base.try_append(&mut table(&renamed, &[3]), true),
+            Err(TableError::IncompatibleColumn(0))
+        ));
+        assert_eq!(base.num_rows(), 2);
+        base.try_append(&mut table(&renamed, &[3]), false).unwrap();
+        assert_eq!(base.num_rows(), 3);
+        base.try_append(&mut table(&schema, &[4]), true).unwrap();
+        assert_eq!(base.num_rows(), 4);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::Column;
-    use ahash::AHasher;
-    use arrow::datatypes::{Field, Float64Type, UInt32Type, UInt64Type};
-    use chrono::NaiveDate;
-    use std::hash::{Hash, Hasher};
-    use std::net::{IpAddr, Ipv4Addr};
+    #[test]
+    fn rechunk() {
+        let mut first = Column::try_from_slice::<Int64Type>(&[0, 1, 2]).unwrap();
+        first
+            .try_append(&mut Column::try_from_slice::<Int64Type>(&[3, 4, 5, 6]).unwrap())
+            .unwrap();
+        let mut second = Column::try_from_slice::<Float64Type>(&[0.0]).unwrap();
+        second
+            .try_append(&mut Column::try_from_slice::<Float64Type>(&[1.0, 2.0, 3.0, 4.0]).unwrap())
+            .unwrap();
+        second
+            .try_append(&mut Column::try_from_slice::<Float64Type>(&[5.0, 6.0]).unwrap())
+            .unwrap();
+        let mut table = Table::<u64>::new(
+            Arc::new(Schema::empty()),
+            vec![first.clone(), second.clone()],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
 
-    fn hash(seq: &str) -> u64 {
-        let mut hasher = AHasher::default();
-        seq.hash(&mut hasher);
-        hasher.finish()
+        table.rechunk(3).unwrap();
+        for column in &table.columns {
+            assert_eq!(column.cumlen, [0, 3, 6, 7]);
+            assert_eq!(
+                column
+                    .arrays
+                    .iter()
+                    .map(|a| Array::len(a.as_ref()))
+                    .collect::<Vec<_>>(),
+                [3, 3, 1]
+            );
+        }
+        assert_eq!(table.columns[0], first);
+        assert_eq!(table.columns[1], second);
+        assert_eq!(
+            table.columns[1]
+                .primitive_try_get::<Float64Type>(4)
+                .unwrap(),
+            Some(4.0)
+        );
+
+        table.rechunk(0).unwrap();
+        assert!(table.columns.iter().all(|c| c.arrays.len() == 1));
+        table.rechunk(10).unwrap();
+        assert!(table.columns.iter().all(|c| c.arrays.len() == 1));
+        assert_eq!(table.columns[0], first);
     }
 
     #[test]
-    fn table_new() {
-        let table = Table::<usize>::new(Arc::new(Schema::empty()), Vec::new(), HashMap::new())
+    fn max_arrays_per_column() {
+        let table = |values: &[i64]| {
+            Table::<u64>::new(
+                Arc::new(Schema::empty()),
+                vec![Column::try_from_slice::<Int64Type>(values).unwrap()],
+                HashMap::new(),
+            )
+            .expect("invalid columns")
+        };
+        let mut capped = table(&[0]).with_max_arrays_per_column(4);
+        let mut uncapped = table(&[0]);
+        for i in 1..20 {
+            capped.append(&mut table(&[i]));
+            uncapped.append(&mut table(&[i]));
+            assert!(capped.columns[0].arrays.len() <= 4);
+        }
+        assert_eq!(uncapped.columns[0].arrays.len(), 20);
+        assert_eq!(capped.num_rows(), 20);
+        assert_eq!(capped.columns[0], uncapped.columns[0]);
+        assert_eq!(
+            capped.columns[0]
+                .primitive_try_get::<Int64Type>(17)
+                .unwrap(),
+            Some(17)
+        );
+    }
+
+    #[test]
+    fn append_checks_all_columns_first() {
+        let table = |c1: Column| {
+            let c0 = Column::try_from_slice::<Int64Type>(&[1, 2]).unwrap();
+            Table::<usize>::new(Arc::new(Schema::empty()), vec![c0, c1], HashMap::new())
+                .expect("invalid columns")
+        };
+        let mut base = table(Column::try_from_slice::<Int64Type>(&[10, 20]).unwrap());
+        let mut other = table(Column::from(
+            Arc::new(StringArray::from(vec!["a", "b"])) as Arc<dyn Array>
+        ));
+
+        assert!(matches!(
+            base.try_append(&mut other, false),
+            Err(TableError::IncompatibleColumn(1))
+        ));
+        assert_eq!(base.num_rows(), 2);
+        assert_eq!(base.column(0).unwrap().len(), 2);
+        assert_eq!(other.column(0).unwrap().len(), 2);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            base.append(&mut other);
+        }));
+        assert!(result.is_err());
+        assert_eq!(base.column(0).unwrap().len(), 2);
+        assert_eq!(base.column(1).unwrap().len(), 2);
+
+        let mut other = table(Column::try_from_slice::<Int64Type>(&[30, 40]).unwrap());
+        base.append(&mut other);
+        assert_eq!(base.num_rows(), 4);
+        assert_eq!(other.num_rows(), 0);
+    }
+
+    #[test]
+    fn schema_drift() {
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+            Field::new("len", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        let table = Table::<usize>::new(Arc::new(schema.clone()), Vec::new(), HashMap::new())
             .expect("creating an empty `Table` should not fail");
-        assert_eq!(table.num_columns(), 0);
-        assert_eq!(table.num_rows(), 0);
+        assert_eq!(table.check_compatible(&schema), Ok(()));
+
+        let mut fields = schema.fields().to_vec();
+        fields.push(Arc::new(Field::new("port", DataType::UInt32, false)));
+        let drift = table.check_compatible(&Schema::new(fields)).unwrap_err();
+        assert_eq!(drift.added, vec!["port".to_string()]);
+        assert!(drift.removed.is_empty() && drift.retyped.is_empty());
+
+        let drift = table
+            .check_compatible(&Schema::new(vec![
+                Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+                Field::new("len", DataType::Int64, false),
+            ]))
+            .unwrap_err();
+        assert_eq!(drift.removed, vec!["name".to_string()]);
+        assert!(drift.added.is_empty() && drift.retyped.is_empty());
+
+        let drift = table
+            .check_compatible(&Schema::new(vec![
+                Field::new("ts", DataType::Timestamp(TimeUnit::Second, None), false),
+                Field::new("len", DataType::Float64, false),
+                Field::new("name", DataType::Utf8, false),
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            drift.retyped,
+            vec![("len".to_string(), DataType::Int64, DataType::Float64)]
+        );
+        assert_eq!(
+            drift.to_string(),
+            "schema drift: 0 added, 0 removed, 1 retyped"
+        );
     }
 
     #[test]
-    fn column_new() {
-        let column = Column::default();
-        assert_eq!(column.len(), 0);
-        assert_eq!(column.primitive_try_get::<UInt32Type>(0), Ok(None));
+    fn subnet_membership() {
+        let addrs: Vec<u32> = [
+            Ipv4Addr::new(192, 168, 1, 1),
+            Ipv4Addr::new(192, 168, 2, 1),
+            Ipv4Addr::new(192, 168, 1, 254),
+            Ipv4Addr::new(10, 0, 0, 1),
+        ]
+        .into_iter()
+        .map(u32::from)
+        .collect();
+        let column = Column::try_from_slice::<UInt32Type>(&addrs).unwrap();
+        let rows = [0_usize, 1, 2, 3];
 
-        let column = Column::default();
-        assert_eq!(column.len(), 0);
-        assert_eq!(column.string_try_get(0), Ok(None));
+        let subnet = Ipv4Addr::new(192, 168, 1, 0);
+        assert_eq!(column.rows_in_subnet(&rows, subnet, 24), Ok(vec![0, 2]));
+        assert_eq!(column.rows_in_subnet(&[1, 2], subnet, 24), Ok(vec![2]));
+        assert_eq!(column.rows_in_subnet(&rows, subnet, 16), Ok(vec![0, 1, 2]));
+        assert_eq!(
+            column.rows_in_subnet(&rows, Ipv4Addr::UNSPECIFIED, 0),
+            Ok(vec![0, 1, 2, 3])
+        );
+        assert_eq!(
+            column.rows_in_subnet(&rows, Ipv4Addr::new(10, 0, 0, 1), 32),
+            Ok(vec![3])
+        );
+        assert_eq!(
+            Column::try_from_slice::<Int64Type>(&[1])
+                .unwrap()
+                .rows_in_subnet(&[0], subnet, 24),
+            Err(TypeError())
+        );
     }
 
     #[test]
@@ -711,6 +5382,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::too_many_lines)]
     fn count_group_by_test() {
         let schema = Schema::new(vec![
             Field::new("", DataType::Timestamp(TimeUnit::Second, None), false),
@@ -797,6 +5469,262 @@ mod tests {
         assert_eq!(5_usize, group_count[0].series[0].count);
         assert_eq!(43_usize, group_count[1].series[0].count);
         assert_eq!(48_usize, group_count[2].series[0].count);
+
+        let checked = table
+            .try_count_group_by(&rows, &column_types, 0, Some(30), &Arc::new(vec![0, 1, 2]))
+            .expect("valid column indices");
+        assert_eq!(checked, group_count);
+        assert!(matches!(
+            table.try_count_group_by(&rows, &column_types, 0, Some(30), &Arc::new(vec![1, 5])),
+            Err(TableError::ColumnIndexOutOfRange(5))
+        ));
+        assert!(matches!(
+            table.try_count_group_by(&rows, &column_types, 3, Some(30), &Arc::new(vec![1])),
+            Err(TableError::ColumnIndexOutOfRange(3))
+        ));
+
+        let count_columns = Arc::new(vec![0, 1]);
+        assert_eq!(
+            table.count_group_by_interval(
+                &rows,
+                &column_types,
+                0,
+                Some(Interval::Hours(1)),
+                &count_columns
+            ),
+            table.count_group_by(&rows, &column_types, 0, Some(3600), &count_columns)
+        );
+        assert_eq!(
+            table.count_group_by_interval(
+                &rows,
+                &column_types,
+                0,
+                Some(Interval::Minutes(1)),
+                &count_columns
+            ),
+            table.count_group_by(&rows, &column_types, 0, Some(60), &count_columns)
+        );
+        let group_count = table.count_group_by_interval(
+            &rows,
+            &column_types,
+            0,
+            Some(Interval::Days(3)),
+            &count_columns,
+        );
+        assert_eq!(
+            group_count[0].series[0].value,
+            GroupElement::DateTime(
+                NaiveDate::from_ymd_opt(2019, 12, 31)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            table.count_group_by(&rows, &column_types, 0, Some(3 * 86_400), &count_columns),
+            table.count_group_by_interval(
+                &rows,
+                &column_types,
+                0,
+                Some(Interval::Days(1)),
+                &count_columns
+            )
+        );
+    }
+
+    #[test]
+    fn mode_group_by() {
+        let time = |h, m| {
+            NaiveDate::from_ymd_opt(2019, 9, 22)
+                .unwrap()
+                .and_hms_opt(h, m, 0)
+                .unwrap()
+        };
+        let nanos: Vec<i64> = [(0, 5), (0, 10), (0, 50), (1, 0), (1, 30), (2, 0)]
+            .into_iter()
+            .map(|(h, m)| time(h, m).and_utc().timestamp_nanos_opt().unwrap())
+            .collect();
+        let times = Column::try_from_slice::<Int64Type>(&nanos).unwrap();
+        let kinds = Column::try_from_slice_with_nulls::<UInt64Type>(
+            &[2, 1, 2, 3, 1, 4],
+            &[true, true, true, true, true, false],
+        )
+        .unwrap();
+        let table = Table::<usize>::new(
+            Arc::new(Schema::empty()),
+            vec![times, kinds],
+            HashMap::new(),
+        )
+        .expect("invalid columns");
+        let column_types = Arc::new(vec![ColumnType::DateTime, ColumnType::Enum]);
+        let rows: Vec<usize> = (0..6).collect();
+
+        assert_eq!(
+            table
+                .mode_group_by(&rows, &column_types, 0, Some(Interval::Hours(1)), 1)
+                .unwrap(),
+            [
+                (GroupElement::DateTime(time(0, 0)), Element::UInt(2)),
+                (GroupElement::DateTime(time(1, 0)), Element::UInt(1)),
+            ]
+        );
+        assert!(table
+            .mode_group_by(&rows, &column_types, 0, None, 1)
+            .unwrap()
+            .is_empty());
+        assert!(table
+            .mode_group_by(&rows, &column_types, 1, Some(Interval::Hours(1)), 0)
+            .unwrap()
+            .is_empty());
+        assert!(table
+            .mode_group_by(&rows, &column_types, 0, Some(Interval::Hours(1)), 2)
+            .is_err());
+        let wrong_types = Arc::new(vec![ColumnType::DateTime, ColumnType::Utf8]);
+        assert!(matches!(
+            table.mode_group_by(&rows, &wrong_types, 0, Some(Interval::Hours(1)), 1),
+            Err(TableError::UnexpectedType(1))
+        ));
+    }
+
+    #[test]
+    fn write_statistics_ndjson() {
+        let schema = Schema::new(vec![
+            Field::new("count", DataType::Int64, false),
+            Field::new("ratio", DataType::Float64, false),
+        ]);
+        let c0 = Column::try_from_slice::<Int64Type>(&[1, 2, 2]).unwrap();
+        let c1 = Column::try_from_slice::<Float64Type>(&[0.5, 1.5, 2.5]).unwrap();
+        let table = Table::<usize>::new(Arc::new(schema), vec![c0, c1], HashMap::new())
+            .expect("invalid columns");
+        let mut out = Vec::new();
+        table
+            .write_statistics_ndjson(
+                &mut out,
+                &[0, 1, 2],
+                &Arc::new(vec![ColumnType::Int64, ColumnType::Float64]),
+                &HashMap::new(),
+                &Arc::new(Vec::new()),
+                &Arc::new(vec![10, 10]),
+                2,
+            )
+            .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let lines = out
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(out.ends_with('\n'));
+        assert_eq!(lines[0]["column"], 0);
+        assert_eq!(lines[0]["name"], "count");
+        assert_eq!(lines[0]["type"], "int64");
+        assert_eq!(lines[0]["description"]["count"], 3);
+        assert_eq!(lines[0]["n_largest_count"]["number_of_elements"], 2);
+        assert_eq!(lines[1]["name"], "ratio");
+        assert_eq!(lines[1]["type"], "float64");
+    }
+
+    #[test]
+    fn statistics_config_mismatch() {
+        let c0 = Column::try_from_slice::<Int64Type>(&[0, 1]).unwrap();
+        let c1 = Column::try_from_slice::<Int64Type>(&[10, 20]).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![c0, c1], HashMap::new())
+            .expect("invalid columns");
+        let statistics = |column_types: Vec<ColumnType>, time_intervals, numbers_of_top_n| {
+            table.statistics(
+                &[0, 1],
+                &Arc::new(column_types),
+                &HashMap::new(),
+                &Arc::new(time_intervals),
+                &Arc::new(numbers_of_top_n),
+                2,
+            )
+        };
+
+        let types = vec![ColumnType::DateTime, ColumnType::Int64];
+        assert!(statistics(types.clone(), vec![3600], vec![10, 10]).is_ok());
+        assert!(matches!(
+            statistics(types.clone(), vec![3600], vec![10]),
+            Err(TableError::NumTopNMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            statistics(types, vec![], vec![10, 10]),
+            Err(TableError::NumTimeIntervalsMismatch {
+                expected: 1,
+                found: 0
+            })
+        ));
+        assert!(matches!(
+            statistics(vec![ColumnType::Int64], vec![], vec![10, 10]),
+            Err(TableError::NumColumnsMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+
+        let types = [ColumnType::Int64, ColumnType::Int64];
+        assert!(table
+            .lazy_statistics(&[0, 1], &types, &HashMap::new(), &[], &[10, 10], 2)
+            .is_ok());
+        assert!(matches!(
+            table.lazy_statistics(&[0, 1], &types, &HashMap::new(), &[3600], &[10, 10], 2),
+            Err(TableError::NumTimeIntervalsMismatch {
+                expected: 0,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn statistics_in_time_range() {
+        let hour = |h| {
+            NaiveDate::from_ymd_opt(2019, 9, 22)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+        };
+        let nanos: Vec<i64> = [0, 1, 2, 2, 3]
+            .into_iter()
+            .map(|h| hour(h).and_utc().timestamp_nanos_opt().unwrap())
+            .collect();
+        let c0 = Column::try_from_slice_with_nulls::<Int64Type>(
+            &[nanos, vec![0]].concat(),
+            &[true, true, true, true, true, false],
+        )
+        .unwrap();
+        let c1 = Column::try_from_slice::<Int64Type>(&[10, 20, 30, 40, 50, 60]).unwrap();
+        let table = Table::<usize>::new(Arc::new(Schema::empty()), vec![c0, c1], HashMap::new())
+            .expect("invalid columns");
+        let column_types = Arc::new(vec![ColumnType::DateTime, ColumnType::Int64]);
+        let statistics = |time_column, start, end| {
+            table.statistics_in_time_range(
+                time_column,
+                start,
+                end,
+                &column_types,
+                &HashMap::new(),
+                &Arc::new(vec![3600]),
+                &Arc::new(vec![10, 10]),
+                2,
+            )
+        };
+
+        let stats = statistics(0, hour(1), hour(3)).unwrap();
+        assert_eq!(stats[1].description.count(), 3);
+        assert_eq!(stats[1].description.min(), Some(&Element::Int(20)));
+        assert_eq!(stats[1].description.max(), Some(&Element::Int(40)));
+        assert_eq!(stats[0].n_largest_count.number_of_elements(), 2);
+
+        let stats = statistics(0, hour(5), hour(6)).unwrap();
+        assert_eq!(stats[1].description.count(), 0);
+        assert!(matches!(
+            statistics(2, hour(0), hour(1)),
+            Err(TableError::ColumnIndexOutOfRange(2))
+        ));
     }
 
     #[test]
@@ -815,7 +5743,7 @@ mod tests {
         let c0_v: Vec<i64> = vec![1, 3, 3, 5, 2, 1, 3];
         let c1_v: Vec<_> = vec!["111a qwer", "b", "c", "d", "b", "111a qwer", "111a qwer"];
         let c2_v: Vec<u32> = vec![
-            Ipv4Addr::new(127, 0, 0, 1).into(),
+            Ipv4Addr::LOCALHOST.into(),
             Ipv4Addr::new(127, 0, 0, 2).into(),
             Ipv4Addr::new(127, 0, 0, 3).into(),
             Ipv4Addr::new(127, 0, 0, 4).into(),
@@ -915,14 +5843,16 @@ mod tests {
         let rows = vec![0_usize, 3, 1, 4, 2, 6, 5];
         let time_intervals = Arc::new(vec![3600]);
         let numbers_of_top_n = Arc::new(vec![10; 7]);
-        let stat = table.statistics(
-            &rows,
-            &column_types,
-            &HashMap::new(),
-            &time_intervals,
-            &numbers_of_top_n,
-            2,
-        );
+        let stat = table
+            .statistics(
+                &rows,
+                &column_types,
+                &HashMap::new(),
+                &time_intervals,
+                &numbers_of_top_n,
+                2,
+            )
+            .unwrap();
 
         assert_eq!(4, stat[0].n_largest_count.number_of_elements());
         assert_eq!(
@@ -953,19 +5883,21 @@ mod tests {
             5,
             sid.iter()
                 .zip(tester.iter())
-                .map(|(id, s)| (*id, vec![s.to_string()]))
+                .map(|(id, s)| (*id, vec![s.clone()]))
                 .collect(),
         )]
         .into_iter()
         .collect();
-        let stat = table.statistics(
-            &rows,
-            &column_types,
-            &c5_r_map,
-            &time_intervals,
-            &numbers_of_top_n,
-            2,
-        );
+        let stat = table
+            .statistics(
+                &rows,
+                &column_types,
+                &c5_r_map,
+                &time_intervals,
+                &numbers_of_top_n,
+                2,
+            )
+            .unwrap();
 
         assert_eq!(4, stat[0].n_largest_count.number_of_elements());
         assert_eq!(
@@ -994,5 +5926,25 @@ mod tests {
             Element::Binary(b"111a qwer".to_vec()),
             *stat[6].n_largest_count.mode().unwrap()
         );
+
+        let lazy = table
+            .lazy_statistics(
+                &rows,
+                &column_types,
+                &c5_r_map,
+                &time_intervals,
+                &numbers_of_top_n,
+                2,
+            )
+            .unwrap();
+        assert_eq!(lazy.len(), 7);
+        assert!(!lazy.is_computed(5));
+        let first = lazy.get(5).unwrap();
+        assert!(lazy.is_computed(5));
+        assert!(!lazy.is_computed(0));
+        assert!(std::ptr::eq(first, lazy.get(5).unwrap()));
+        assert_eq!(first.description, stat[5].description);
+        assert_eq!(first.n_largest_count.mode(), stat[5].n_largest_count.mode());
+        assert!(lazy.get(7).is_none());
     }
 }